@@ -0,0 +1,74 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The derives find the crate under its renamed path without a `#[traverse(crate = "...")]`.
+
+use std::any::Any;
+use std::ops::ControlFlow;
+
+use visit::Traversable;
+use visit::TraversableMut;
+use visit::Visitor;
+use visit::VisitorMut;
+
+#[derive(Traversable, TraversableMut)]
+struct Leaf(u32);
+
+#[derive(Traversable, TraversableMut)]
+enum Tree {
+    Leaf(Leaf),
+    Node(Vec<Tree>),
+}
+
+#[derive(Default)]
+struct Sum(u32);
+
+impl Visitor for Sum {
+    type Break = ();
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(leaf) = this.downcast_ref::<Leaf>() {
+            self.0 += leaf.0;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl VisitorMut for Sum {
+    type Break = ();
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(leaf) = this.downcast_mut::<Leaf>() {
+            leaf.0 += 1;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_renamed_crate() {
+    let mut tree = Tree::Node(vec![
+        Tree::Leaf(Leaf(1)),
+        Tree::Node(vec![Tree::Leaf(Leaf(2)), Tree::Leaf(Leaf(3))]),
+    ]);
+
+    let mut visitor = Sum::default();
+    assert!(tree.traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.0, 6);
+
+    assert!(tree.traverse_mut(&mut Sum::default()).is_continue());
+    let mut visitor = Sum::default();
+    assert!(tree.traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.0, 9);
+}
@@ -16,6 +16,8 @@ use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::iter::IntoIterator;
 
+use proc_macro_crate::FoundCrate;
+use proc_macro_crate::crate_name;
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
@@ -29,8 +31,10 @@ use syn::Error;
 use syn::Expr;
 use syn::Field;
 use syn::Fields;
+use syn::Generics;
 use syn::Ident;
 use syn::Lit;
+use syn::LitInt;
 use syn::LitStr;
 use syn::Member;
 use syn::Meta;
@@ -39,11 +43,15 @@ use syn::Path;
 use syn::Result;
 use syn::Token;
 use syn::Variant;
+use syn::WherePredicate;
+use syn::ext::IdentExt;
 use syn::parse_macro_input;
 use syn::parse_quote;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Mut;
+use syn::visit;
+use syn::visit::Visit;
 
 #[proc_macro_derive(Traversable, attributes(traverse))]
 pub fn derive_traversable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -152,7 +160,8 @@ impl Iterator for Params {
 enum Param {
     Unit(Span),
     StringLiteral(Span, LitStr),
-    NestedParams(Span),
+    IntLiteral(Span, LitInt),
+    NestedParams(Span, MetaList),
 }
 
 impl Param {
@@ -160,13 +169,13 @@ impl Param {
         let span = meta.span();
         match meta {
             Meta::Path(_) => Ok(Param::Unit(span)),
-            Meta::List(_) => Ok(Param::NestedParams(span)),
+            Meta::List(meta_list) => Ok(Param::NestedParams(span, meta_list)),
             Meta::NameValue(name_value) => {
                 if let Expr::Lit(expr_lit) = &name_value.value {
-                    if let Lit::Str(lit_str) = &expr_lit.lit {
-                        Ok(Param::StringLiteral(span, lit_str.clone()))
-                    } else {
-                        Err(Error::new_spanned(name_value, "invalid parameter"))
+                    match &expr_lit.lit {
+                        Lit::Str(lit_str) => Ok(Param::StringLiteral(span, lit_str.clone())),
+                        Lit::Int(lit_int) => Ok(Param::IntLiteral(span, lit_int.clone())),
+                        _ => Err(Error::new_spanned(name_value, "invalid parameter")),
                     }
                 } else {
                     Err(Error::new_spanned(name_value, "invalid parameter"))
@@ -177,7 +186,10 @@ impl Param {
 
     fn span(&self) -> Span {
         match self {
-            Self::Unit(span) | Self::StringLiteral(span, _) | Self::NestedParams(span) => *span,
+            Self::Unit(span)
+            | Self::StringLiteral(span, _)
+            | Self::IntLiteral(span, _)
+            | Self::NestedParams(span, _) => *span,
         }
     }
 
@@ -196,16 +208,49 @@ impl Param {
             Err(Error::new(self.span(), "invalid parameter"))
         }
     }
+
+    fn int_literal(self) -> Result<LitInt> {
+        if let Self::IntLiteral(_, lit_int) = self {
+            Ok(lit_int)
+        } else {
+            Err(Error::new(self.span(), "invalid parameter"))
+        }
+    }
+
+    fn paths(self) -> Result<Vec<Path>> {
+        if let Self::NestedParams(_, meta_list) = self {
+            Ok(meta_list
+                .parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?
+                .into_iter()
+                .collect())
+        } else {
+            Err(Error::new(self.span(), "invalid parameter"))
+        }
+    }
 }
 
-#[inline(always)]
-fn resolve_crate_name() -> Path {
-    parse_quote!(::traversable)
+// The path of the `traversable` crate, from the `crate` parameter if given, otherwise from the
+// name of the dependency in the manifest of the crate being compiled, which may be renamed.
+fn resolve_crate_name(param: Option<Param>) -> Result<Path> {
+    if let Some(param) = param {
+        return param.string_literal()?.parse::<Path>();
+    }
+    Ok(match crate_name("traversable") {
+        Ok(FoundCrate::Name(name)) => {
+            let name = Ident::new(&name, Span::call_site());
+            parse_quote!(::#name)
+        }
+        // The crate itself only uses the derive in its tests, benchmarks and examples, which refer
+        // to it as an external crate.
+        Ok(FoundCrate::Itself) | Err(_) => parse_quote!(::traversable),
+    })
 }
 
 fn impl_traversable(input: DeriveInput, mutable: bool) -> Result<TokenStream> {
     let mut params = Params::from_attrs(input.attrs, "traverse")?;
-    params.validate(&["skip"])?;
+    params.validate(&["skip", "as_dyn", "crate", "bound", "reverse", "visits"])?;
+
+    let crate_name = resolve_crate_name(params.param("crate")?)?;
 
     let skip_visit_self = params
         .param("skip")?
@@ -213,8 +258,48 @@ fn impl_traversable(input: DeriveInput, mutable: bool) -> Result<TokenStream> {
         .transpose()?
         .is_some();
 
+    // Types that are not visited themselves only compute their `VISITS` from their fields if they
+    // opt in with `visits = "fields"`, since the `VISITS` of types that refer to each other through
+    // their fields cannot be evaluated.
+    let visits_fields = match params.param("visits")? {
+        Some(param) => {
+            let lit_str = param.string_literal()?;
+            match lit_str.value().as_str() {
+                "fields" if skip_visit_self => true,
+                "fields" => {
+                    return Err(Error::new(
+                        lit_str.span(),
+                        "`visits = \"fields\"` requires `skip`",
+                    ));
+                }
+                "any" => false,
+                _ => return Err(Error::new(lit_str.span(), "invalid parameter")),
+            }
+        }
+        None => false,
+    };
+
+    let as_dyn = params
+        .param("as_dyn")?
+        .map(Param::paths)
+        .transpose()?
+        .unwrap_or_default();
+
+    let reverse = params
+        .param("reverse")?
+        .map(Param::unit)
+        .transpose()?
+        .is_some();
+
     let name = input.ident;
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = impl_generics(
+        &input.generics,
+        &input.data,
+        params.param("bound")?,
+        mutable,
+        &crate_name,
+    )?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let visitor = Ident::new(
         if mutable { "VisitorMut" } else { "Visitor" },
@@ -222,17 +307,23 @@ fn impl_traversable(input: DeriveInput, mutable: bool) -> Result<TokenStream> {
     );
 
     let enter_method = Ident::new(
-        if mutable { "enter_mut" } else { "enter" },
+        if mutable {
+            "enter_node_mut"
+        } else {
+            "enter_node"
+        },
         Span::call_site(),
     );
 
     let leave_method = Ident::new(
-        if mutable { "leave_mut" } else { "leave" },
+        if mutable {
+            "leave_node_mut"
+        } else {
+            "leave_node"
+        },
         Span::call_site(),
     );
 
-    let crate_name = resolve_crate_name();
-
     let enter_self = if skip_visit_self {
         None
     } else {
@@ -249,9 +340,29 @@ fn impl_traversable(input: DeriveInput, mutable: bool) -> Result<TokenStream> {
         })
     };
 
+    let visits = if visits_fields {
+        let visits = visits_data(&name, &input.data, mutable, &crate_name)?;
+        Some(quote! {
+            const VISITS: #crate_name::Visits = #crate_name::Visits::NOTHING #visits;
+        })
+    } else {
+        None
+    };
+
+    // The schema is only provided by immutable traversal.
+    let schema = if mutable {
+        None
+    } else {
+        let schema = type_schema(&name, &input.data, &crate_name)?;
+        Some(quote! {
+            const SCHEMA: ::core::option::Option<&'static #crate_name::schema::TypeSchema> =
+                ::core::option::Option::Some(&#schema);
+        })
+    };
+
     let traverse_fields = match input.data {
-        Data::Struct(struct_) => traverse_struct(struct_, mutable),
-        Data::Enum(enum_) => traverse_enum(enum_, mutable),
+        Data::Struct(struct_) => traverse_struct(struct_, mutable, reverse, &crate_name),
+        Data::Enum(enum_) => traverse_enum(enum_, mutable, reverse, &crate_name),
         Data::Union(union_) => {
             return Err(Error::new_spanned(
                 union_.union_token,
@@ -280,8 +391,40 @@ fn impl_traversable(input: DeriveInput, mutable: bool) -> Result<TokenStream> {
         None
     };
 
+    // Casts to trait objects are only registered for immutable traversal.
+    let (register_casts, cast_impls) = if mutable || as_dyn.is_empty() {
+        (None, None)
+    } else {
+        (
+            Some(quote! {
+                fn register_casts(registry: &mut #crate_name::cast::CastRegistry) {
+                    #( registry.register::<Self, dyn #as_dyn>(); )*
+                }
+            }),
+            Some(quote! {
+                #(
+                    impl #impl_generics #crate_name::cast::CastTo<dyn #as_dyn>
+                        for #name #ty_generics #where_clause
+                    {
+                        fn cast_to(&self) -> &(dyn #as_dyn + 'static) {
+                            self
+                        }
+                    }
+                )*
+            }),
+        )
+    };
+
     Ok(quote! {
+        #cast_impls
+
         impl #impl_generics #crate_name::#impl_trait for #name #ty_generics #where_clause {
+            #visits
+
+            #schema
+
+            #register_casts
+
             fn #method<V: #crate_name::#visitor>(
                 & #mut_modifier self,
                 visitor: &mut V
@@ -295,10 +438,274 @@ fn impl_traversable(input: DeriveInput, mutable: bool) -> Result<TokenStream> {
     })
 }
 
-fn traverse_struct(s: DataStruct, mutable: bool) -> Result<TokenStream> {
-    s.fields
-        .into_iter()
+// The generics of the impl, with the bounds given by the `bound` parameter of the container if
+// any, otherwise with inferred bounds. Like serde, every type parameter mentioned by a traversed
+// field must implement the derived trait, unless the field has its own `bound` or a `with`
+// function. The other type parameters must still be `'static` for the type to be `Any`.
+fn impl_generics(
+    generics: &Generics,
+    data: &Data,
+    bound: Option<Param>,
+    mutable: bool,
+    crate_name: &Path,
+) -> Result<Generics> {
+    let predicates = match bound.map(|bound| parse_bound(bound, mutable)).transpose()? {
+        Some(Some(predicates)) => predicates,
+        _ => infer_bounds(generics, data, mutable, crate_name)?,
+    };
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    Ok(generics)
+}
+
+// Parse `bound = "..."`, which applies to both traits, or `bound(traverse = "...", traverse_mut =
+// "...")`. Returns `None` if no bound is given for the derived trait.
+fn parse_bound(param: Param, mutable: bool) -> Result<Option<Vec<WherePredicate>>> {
+    let bound = match param {
+        Param::NestedParams(_, meta_list) => {
+            let mut params = Params::from_meta_list(meta_list)?;
+            params.validate(&["traverse", "traverse_mut"])?;
+            match params.param(if mutable { "traverse_mut" } else { "traverse" })? {
+                Some(param) => param.string_literal()?,
+                None => return Ok(None),
+            }
+        }
+        param => param.string_literal()?,
+    };
+    let predicates = bound.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+    Ok(Some(predicates.into_iter().collect()))
+}
+
+fn infer_bounds(
+    generics: &Generics,
+    data: &Data,
+    mutable: bool,
+    crate_name: &Path,
+) -> Result<Vec<WherePredicate>> {
+    let impl_trait = Ident::new(
+        if mutable {
+            "TraversableMut"
+        } else {
+            "Traversable"
+        },
+        Span::call_site(),
+    );
+
+    let mut predicates = Vec::new();
+    let mut used = TypeParams {
+        params: generics.type_params().map(|param| &param.ident).collect(),
+        used: Vec::new(),
+    };
+    for field in traversed_fields(data, mutable)? {
+        let mut params = Params::from_attrs(field.attrs.clone(), "traverse")?;
+        if let Some(bound) = params.param("bound")? {
+            if let Some(bound) = parse_bound(bound, mutable)? {
+                predicates.extend(bound);
+                continue;
+            }
+        }
+        if field_with(&mut params, mutable)?.is_none() {
+            used.visit_type(&field.ty);
+        }
+    }
+
+    for param in generics.type_params() {
+        let ident = &param.ident;
+        predicates.push(if used.used.contains(&ident) {
+            parse_quote! { #ident: #crate_name::#impl_trait }
+        } else {
+            parse_quote! { #ident: 'static }
+        });
+    }
+    Ok(predicates)
+}
+
+// The fields that are traversed, i.e., not skipped and not in skipped variants.
+fn traversed_fields(data: &Data, mutable: bool) -> Result<Vec<&Field>> {
+    let fields: Vec<&Field> = match data {
+        Data::Struct(struct_) => struct_.fields.iter().collect(),
+        Data::Enum(enum_) => {
+            let mut fields = Vec::new();
+            for variant in &enum_.variants {
+                let mut params = Params::from_attrs(variant.attrs.clone(), "traverse")?;
+                if params.param("skip")?.is_none() {
+                    fields.extend(variant.fields.iter());
+                }
+            }
+            fields
+        }
+        Data::Union(_) => Vec::new(),
+    };
+    let mut traversed = Vec::new();
+    for field in fields {
+        let mut params = Params::from_attrs(field.attrs.clone(), "traverse")?;
+        if !skip_field(&mut params, mutable)? {
+            traversed.push(field);
+        }
+    }
+    Ok(traversed)
+}
+
+// Collects the type parameters mentioned by the visited types.
+struct TypeParams<'a> {
+    params: Vec<&'a Ident>,
+    used: Vec<&'a Ident>,
+}
+
+impl<'ast> Visit<'ast> for TypeParams<'_> {
+    fn visit_path(&mut self, path: &'ast Path) {
+        if path.leading_colon.is_none() {
+            if let Some(segment) = path.segments.first() {
+                if let Some(param) = self.params.iter().find(|param| **param == &segment.ident) {
+                    if !self.used.contains(param) {
+                        self.used.push(param);
+                    }
+                }
+            }
+        }
+        visit::visit_path(self, path);
+    }
+}
+
+// Union of the `VISITS` of all traversed fields, for types that are not visited themselves and opt
+// in with `visits = "fields"`.
+fn visits_data(name: &Ident, data: &Data, mutable: bool, crate_name: &Path) -> Result<TokenStream> {
+    match data {
+        Data::Struct(struct_) => visits_fields(name, &struct_.fields, mutable, crate_name),
+        Data::Enum(enum_) => enum_
+            .variants
+            .iter()
+            .map(|variant| {
+                let mut params = Params::from_attrs(variant.attrs.clone(), "traverse")?;
+                if params.param("skip")?.map(Param::unit).is_some() {
+                    Ok(TokenStream::new())
+                } else {
+                    visits_fields(name, &variant.fields, mutable, crate_name)
+                }
+            })
+            .collect(),
+        Data::Union(_) => Ok(TokenStream::new()),
+    }
+}
+
+// Fields whose type mentions the deriving type, e.g., `Box<Self>`, contribute `Visits::ANY`, since
+// evaluating their `VISITS` would depend on the `VISITS` being defined.
+fn visits_fields(
+    name: &Ident,
+    fields: &Fields,
+    mutable: bool,
+    crate_name: &Path,
+) -> Result<TokenStream> {
+    let impl_trait = Ident::new(
+        if mutable {
+            "TraversableMut"
+        } else {
+            "Traversable"
+        },
+        Span::call_site(),
+    );
+    fields
+        .iter()
+        .map(|field| {
+            let mut params = Params::from_attrs(field.attrs.clone(), "traverse")?;
+            if skip_field(&mut params, mutable)? {
+                return Ok(TokenStream::new());
+            }
+            let ty = &field.ty;
+            let mut recursive = MentionsType { name, found: false };
+            recursive.visit_type(ty);
+            Ok(
+                if recursive.found || field_with(&mut params, mutable)?.is_some() {
+                    quote! { .union(#crate_name::Visits::ANY) }
+                } else {
+                    quote! { .union(<#ty as #crate_name::#impl_trait>::VISITS) }
+                },
+            )
+        })
+        .collect()
+}
+
+// Finds whether a type mentions `Self` or the type with the given name.
+struct MentionsType<'a> {
+    name: &'a Ident,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for MentionsType<'_> {
+    fn visit_path_segment(&mut self, segment: &'ast syn::PathSegment) {
+        if segment.ident == "Self" || segment.ident == *self.name {
+            self.found = true;
+        }
+        visit::visit_path_segment(self, segment);
+    }
+}
+
+fn type_schema(name: &Ident, data: &Data, crate_name: &Path) -> Result<TokenStream> {
+    let name = name.unraw().to_string();
+    let kind = match data {
+        Data::Struct(struct_) => {
+            let fields = fields_schema(&struct_.fields, crate_name)?;
+            quote! { #crate_name::schema::SchemaKind::Struct(&[#fields]) }
+        }
+        Data::Enum(enum_) => {
+            let variants = enum_
+                .variants
+                .iter()
+                .map(|variant| {
+                    let mut params = Params::from_attrs(variant.attrs.clone(), "traverse")?;
+                    let name = variant.ident.unraw().to_string();
+                    let fields = fields_schema(&variant.fields, crate_name)?;
+                    let skipped = params.param("skip")?.map(Param::unit).is_some();
+                    Ok(quote! {
+                        #crate_name::schema::VariantSchema {
+                            name: #name,
+                            fields: &[#fields],
+                            skipped: #skipped,
+                        },
+                    })
+                })
+                .collect::<Result<TokenStream>>()?;
+            quote! { #crate_name::schema::SchemaKind::Enum(&[#variants]) }
+        }
+        Data::Union(_) => return Ok(TokenStream::new()),
+    };
+    Ok(quote! {
+        #crate_name::schema::TypeSchema {
+            name: #name,
+            kind: #kind,
+        }
+    })
+}
+
+fn fields_schema(fields: &Fields, crate_name: &Path) -> Result<TokenStream> {
+    fields
+        .iter()
         .enumerate()
+        .map(|(index, field)| {
+            let mut params = Params::from_attrs(field.attrs.clone(), "traverse")?;
+            let edge = field_edge(field, index, crate_name);
+            let ty = &field.ty;
+            let skipped = skip_field(&mut params, false)?;
+            Ok(quote! {
+                #crate_name::schema::FieldSchema {
+                    edge: #edge,
+                    type_name: ::core::any::type_name::<#ty>,
+                    type_id: ::core::any::TypeId::of::<#ty>,
+                    skipped: #skipped,
+                },
+            })
+        })
+        .collect()
+}
+
+fn traverse_struct(
+    s: DataStruct,
+    mutable: bool,
+    reverse: bool,
+    crate_name: &Path,
+) -> Result<TokenStream> {
+    ordered_fields(s.fields, reverse)?
+        .into_iter()
         .map(|(index, field)| {
             let member = field.ident.as_ref().map_or_else(
                 || Member::Unnamed(index.into()),
@@ -309,16 +716,29 @@ fn traverse_struct(s: DataStruct, mutable: bool) -> Result<TokenStream> {
             } else {
                 None
             };
-            traverse_field(&quote! { & #mut_modifier self.#member }, field, mutable)
+            let edge = field_edge(&field, index, crate_name);
+            traverse_field(
+                &quote! { & #mut_modifier self.#member },
+                field,
+                edge,
+                None,
+                mutable,
+                crate_name,
+            )
         })
         .collect()
 }
 
-fn traverse_enum(e: DataEnum, mutable: bool) -> Result<TokenStream> {
+fn traverse_enum(
+    e: DataEnum,
+    mutable: bool,
+    reverse: bool,
+    crate_name: &Path,
+) -> Result<TokenStream> {
     let variants = e
         .variants
         .into_iter()
-        .map(|x| traverse_variant(x, mutable))
+        .map(|x| traverse_variant(x, mutable, reverse, crate_name))
         .collect::<Result<TokenStream>>()?;
     Ok(quote! {
         match self {
@@ -328,19 +748,46 @@ fn traverse_enum(e: DataEnum, mutable: bool) -> Result<TokenStream> {
     })
 }
 
-fn traverse_variant(v: Variant, mutable: bool) -> Result<TokenStream> {
+fn traverse_variant(
+    v: Variant,
+    mutable: bool,
+    reverse: bool,
+    crate_name: &Path,
+) -> Result<TokenStream> {
     let mut params = Params::from_attrs(v.attrs, "traverse")?;
-    params.validate(&["skip"])?;
+    params.validate(&["skip", "skip_if"])?;
     if params.param("skip")?.map(Param::unit).is_some() {
         return Ok(TokenStream::new());
     }
     let name = v.ident;
-    let destructuring = destructure_fields(v.fields.clone())?;
-    let fields = v
-        .fields
+    // The condition is checked by an arm without bindings, so it can borrow the whole value.
+    let skip_if = match params.param("skip_if")? {
+        None => None,
+        Some(skip_if) => {
+            let skip_if = skip_if.string_literal()?.parse::<Path>()?;
+            Some(quote! {
+                Self::#name { .. } if #skip_if(&*self) => {}
+            })
+        }
+    };
+    // The `skip_if` conditions of fields are checked before the fields are bound, for the same
+    // reason, and stored in variables named after the index of the field.
+    let mut skip_if_fields = Vec::new();
+    for (index, field) in v.fields.iter().enumerate() {
+        if let Some(skip_if) = field_skip_if(field, mutable)? {
+            let var = Ident::new(&format!("skip_if_{index}"), Span::call_site());
+            skip_if_fields.push((index, var, skip_if));
+        }
+    }
+    let destructuring = destructure_fields(v.fields.clone(), mutable)?;
+    let fields = ordered_fields(v.fields, reverse)?
         .into_iter()
-        .enumerate()
         .map(|(index, field)| {
+            let edge = field_edge(&field, index, crate_name);
+            let skip_if_var = skip_if_fields
+                .iter()
+                .find(|(skipped, _, _)| *skipped == index)
+                .map(|(_, var, _)| var);
             traverse_field(
                 &field
                     .ident
@@ -348,18 +795,36 @@ fn traverse_variant(v: Variant, mutable: bool) -> Result<TokenStream> {
                     .unwrap_or_else(|| Ident::new(&format!("i{}", index), Span::call_site()))
                     .to_token_stream(),
                 field,
+                edge,
+                skip_if_var,
                 mutable,
+                crate_name,
             )
         })
         .collect::<Result<TokenStream>>()?;
-    Ok(quote! {
-        Self::#name #destructuring => {
-            #fields
+    Ok(if skip_if_fields.is_empty() {
+        quote! {
+            #skip_if
+            Self::#name #destructuring => {
+                #fields
+            }
+        }
+    } else {
+        let vars = skip_if_fields.iter().map(|(_, var, _)| var);
+        let skip_ifs = skip_if_fields.iter().map(|(_, _, skip_if)| skip_if);
+        quote! {
+            #skip_if
+            Self::#name { .. } => {
+                #( let #vars = #skip_ifs(&*self); )*
+                if let Self::#name #destructuring = self {
+                    #fields
+                }
+            }
         }
     })
 }
 
-fn destructure_fields(fields: Fields) -> Result<TokenStream> {
+fn destructure_fields(fields: Fields, mutable: bool) -> Result<TokenStream> {
     Ok(match fields {
         Fields::Named(fields) => {
             let field_list = fields
@@ -368,7 +833,7 @@ fn destructure_fields(fields: Fields) -> Result<TokenStream> {
                 .map(|field| {
                     let mut params = Params::from_attrs(field.attrs, "traverse")?;
                     let field_name = field.ident.unwrap();
-                    Ok(if params.param("skip")?.map(Param::unit).is_some() {
+                    Ok(if skip_field(&mut params, mutable)? {
                         quote! { #field_name: _ }
                     } else {
                         field_name.into_token_stream()
@@ -386,7 +851,7 @@ fn destructure_fields(fields: Fields) -> Result<TokenStream> {
                 .enumerate()
                 .map(|(index, field)| {
                     let mut params = Params::from_attrs(field.attrs, "traverse")?;
-                    Ok(if params.param("skip")?.map(Param::unit).is_some() {
+                    Ok(if skip_field(&mut params, mutable)? {
                         quote! { _ }
                     } else {
                         Ident::new(&format!("i{index}",), Span::call_site()).into_token_stream()
@@ -401,27 +866,137 @@ fn destructure_fields(fields: Fields) -> Result<TokenStream> {
     })
 }
 
-fn traverse_field(value: &TokenStream, field: Field, mutable: bool) -> Result<TokenStream> {
+// The fields with their declaration index, in the order they are traversed: first the fields with
+// an `order` parameter by ascending order, then the others in declaration order, all reversed if
+// the container has the `reverse` parameter.
+fn ordered_fields(fields: Fields, reverse: bool) -> Result<Vec<(usize, Field)>> {
+    let mut ordered = Vec::new();
+    let mut unordered = Vec::new();
+    for (index, field) in fields.into_iter().enumerate() {
+        let mut params = Params::from_attrs(field.attrs.clone(), "traverse")?;
+        match params.param("order")? {
+            None => unordered.push((index, field)),
+            Some(order) => {
+                let lit_int = order.int_literal()?;
+                let order = lit_int.base10_parse::<u64>()?;
+                if ordered.iter().any(|(other, _)| *other == order) {
+                    return Err(Error::new_spanned(lit_int, "duplicate order"));
+                }
+                ordered.push((order, (index, field)));
+            }
+        }
+    }
+    ordered.sort_by_key(|(order, _)| *order);
+    let mut fields: Vec<(usize, Field)> = ordered.into_iter().map(|(_, field)| field).collect();
+    fields.extend(unordered);
+    if reverse {
+        fields.reverse();
+    }
+    Ok(fields)
+}
+
+// The function given by `skip_if` to skip a traversed field depending on its parent.
+fn field_skip_if(field: &Field, mutable: bool) -> Result<Option<Path>> {
+    let mut params = Params::from_attrs(field.attrs.clone(), "traverse")?;
+    if skip_field(&mut params, mutable)? {
+        return Ok(None);
+    }
+    params
+        .param("skip_if")?
+        .map(|skip_if| skip_if.string_literal()?.parse::<Path>())
+        .transpose()
+}
+
+// Whether a field is skipped by `skip`, or by `skip_ref` or `skip_mut` for the derived trait.
+fn skip_field(params: &mut Params, mutable: bool) -> Result<bool> {
+    let mut skip = false;
+    for name in ["skip", if mutable { "skip_mut" } else { "skip_ref" }] {
+        skip |= params.param(name)?.map(Param::unit).is_some();
+    }
+    Ok(skip)
+}
+
+// The function traversing a field given by `with`, or by `with_ref` or `with_mut` for the derived
+// trait, which take precedence.
+fn field_with(params: &mut Params, mutable: bool) -> Result<Option<Param>> {
+    let with = params.param("with")?;
+    let with_trait = params.param(if mutable { "with_mut" } else { "with_ref" })?;
+    Ok(with_trait.or(with))
+}
+
+// The edge to a field, reported to the visitor around traversing it.
+fn field_edge(field: &Field, index: usize, crate_name: &Path) -> TokenStream {
+    match &field.ident {
+        Some(ident) => {
+            let name = ident.unraw().to_string();
+            quote! { #crate_name::Edge::Field(#name) }
+        }
+        None => quote! { #crate_name::Edge::Index(#index) },
+    }
+}
+
+// Traverse a field, unless its `skip_if` function returns `true` for the parent, or `skip_if_var`
+// is `true` if the function was already called.
+fn traverse_field(
+    value: &TokenStream,
+    field: Field,
+    edge: TokenStream,
+    skip_if_var: Option<&Ident>,
+    mutable: bool,
+    crate_name: &Path,
+) -> Result<TokenStream> {
     let mut params = Params::from_attrs(field.attrs, "traverse")?;
-    params.validate(&["skip", "with"])?;
+    params.validate(&[
+        "skip", "skip_ref", "skip_mut", "skip_if", "with", "with_ref", "with_mut", "bound", "order",
+    ])?;
 
-    if params.param("skip")?.map(Param::unit).is_some() {
+    if skip_field(&mut params, mutable)? {
         return Ok(TokenStream::new());
     }
 
-    let crate_name = resolve_crate_name();
-
-    match params.param("with")? {
-        None => Ok(if mutable {
-            quote! { #crate_name::TraversableMut::traverse_mut(#value, visitor)?; }
-        } else {
-            quote! { #crate_name::Traversable::traverse(#value, visitor)?; }
-        }),
+    let traverse = match field_with(&mut params, mutable)? {
+        None => {
+            if mutable {
+                quote! { #crate_name::TraversableMut::traverse_mut(#value, visitor)?; }
+            } else {
+                quote! { #crate_name::Traversable::traverse(#value, visitor)?; }
+            }
+        }
         Some(traverse_fn) => {
             let traverse_fn = traverse_fn.string_literal()?.parse::<Path>()?;
-            Ok(quote! {
+            quote! {
                 #traverse_fn(#value, visitor)?;
-            })
+            }
         }
-    }
+    };
+
+    let traverse = if mutable {
+        quote! {
+            #crate_name::VisitorMut::enter_edge_mut(visitor, #edge);
+            #traverse
+            #crate_name::VisitorMut::leave_edge_mut(visitor);
+        }
+    } else {
+        quote! {
+            #crate_name::Visitor::enter_edge(visitor, #edge);
+            #traverse
+            #crate_name::Visitor::leave_edge(visitor);
+        }
+    };
+
+    Ok(match params.param("skip_if")? {
+        None => traverse,
+        Some(skip_if) => {
+            let skip_if = skip_if.string_literal()?.parse::<Path>()?;
+            let skip = match skip_if_var {
+                Some(var) => var.to_token_stream(),
+                None => quote! { #skip_if(&*self) },
+            };
+            quote! {
+                if !#skip {
+                    #traverse
+                }
+            }
+        }
+    })
 }
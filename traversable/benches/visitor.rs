@@ -0,0 +1,134 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compare visitors dispatching on `&dyn Any` with visitors dispatching on the static node type.
+
+use std::any::Any;
+use std::any::TypeId;
+use std::hint::black_box;
+use std::ops::ControlFlow;
+
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use traversable::Traversable;
+use traversable::Visitor;
+
+#[derive(Traversable)]
+enum Expr {
+    Lit(#[traverse(skip)] u64),
+    Var(Ident),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Traversable)]
+struct Ident(#[traverse(skip)] u64);
+
+#[derive(Traversable)]
+struct Stmt {
+    target: Ident,
+    value: Expr,
+}
+
+fn expr(depth: u64) -> Expr {
+    match depth % 4 {
+        _ if depth == 0 => Expr::Lit(1),
+        0 => Expr::Var(Ident(depth)),
+        1 => Expr::Neg(Box::new(expr(depth - 1))),
+        _ => Expr::Add(Box::new(expr(depth - 1)), Box::new(expr(depth / 2))),
+    }
+}
+
+fn program() -> Vec<Stmt> {
+    (0..1000)
+        .map(|i| Stmt {
+            target: Ident(i),
+            value: expr(i % 16),
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Counts {
+    lits: u64,
+    idents: u64,
+    stmts: u64,
+}
+
+#[derive(Default)]
+struct DynCounter(Counts);
+
+impl Visitor for DynCounter {
+    type Break = ();
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(Expr::Lit(value)) = this.downcast_ref::<Expr>() {
+            self.0.lits += value;
+        } else if let Some(Ident(id)) = this.downcast_ref::<Ident>() {
+            self.0.idents += id;
+        } else if this.is::<Stmt>() {
+            self.0.stmts += 1;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Default)]
+struct StaticCounter(Counts);
+
+impl Visitor for StaticCounter {
+    type Break = ();
+
+    const NODE_HOOKS: bool = true;
+
+    fn enter_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        if TypeId::of::<T>() == TypeId::of::<Expr>() {
+            if let Some(Expr::Lit(value)) = (node as &dyn Any).downcast_ref::<Expr>() {
+                self.0.lits += value;
+            }
+        } else if TypeId::of::<T>() == TypeId::of::<Ident>() {
+            if let Some(Ident(id)) = (node as &dyn Any).downcast_ref::<Ident>() {
+                self.0.idents += id;
+            }
+        } else if TypeId::of::<T>() == TypeId::of::<Stmt>() {
+            self.0.stmts += 1;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn bench_visitor(c: &mut Criterion) {
+    let program = program();
+
+    let mut group = c.benchmark_group("count");
+    group.bench_function("dyn_any", |b| {
+        b.iter(|| {
+            let mut visitor = DynCounter::default();
+            let _ = black_box(&program).traverse(&mut visitor);
+            visitor.0.lits + visitor.0.idents + visitor.0.stmts
+        })
+    });
+    group.bench_function("static", |b| {
+        b.iter(|| {
+            let mut visitor = StaticCounter::default();
+            let _ = black_box(&program).traverse(&mut visitor);
+            visitor.0.lits + visitor.0.idents + visitor.0.stmts
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_visitor);
+criterion_main!(benches);
@@ -0,0 +1,144 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Casting nodes to trait objects.
+//!
+//! `&dyn Any` can only be downcast to concrete types. A [`CastRegistry`] maps the types of nodes
+//! to casters into trait objects, so that nodes can be visited as, e.g., `&dyn Debug` regardless
+//! of their concrete type. Derived types register their casts with
+//! `#[traverse(as_dyn(Debug, ...))]`, which implements [`CastTo`] for each listed trait.
+
+use core::any::Any;
+use core::any::TypeId;
+use core::marker::PhantomData;
+use core::ops::ControlFlow;
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::Finish;
+use crate::Traversable;
+use crate::Visitor;
+
+/// A type that can be cast to the trait object `D`.
+pub trait CastTo<D: ?Sized> {
+    /// Cast `self` to the trait object.
+    fn cast_to(&self) -> &D;
+}
+
+type Caster<D> = for<'a> fn(&'a dyn Any) -> Option<&'a D>;
+
+fn cast_node<T, D>(node: &dyn Any) -> Option<&D>
+where
+    T: CastTo<D> + Any,
+    D: ?Sized,
+{
+    node.downcast_ref::<T>().map(CastTo::cast_to)
+}
+
+/// A registry of casters from the types of nodes to trait objects, keyed by their `TypeId`s.
+#[derive(Default)]
+pub struct CastRegistry {
+    // Keyed by the type ids of the trait object and of the node, holding a `Caster`.
+    casts: BTreeMap<(TypeId, TypeId), Box<dyn Any>>,
+    types: BTreeSet<TypeId>,
+}
+
+impl CastRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the cast of nodes of type `T` to the trait object `D`.
+    pub fn register<T, D>(&mut self)
+    where
+        T: CastTo<D> + Any,
+        D: ?Sized + 'static,
+    {
+        let caster: Caster<D> = cast_node::<T, D>;
+        self.casts
+            .insert((TypeId::of::<D>(), TypeId::of::<T>()), Box::new(caster));
+    }
+
+    /// Register the casts of nodes of type `T` declared by [`Traversable::register_casts`], unless
+    /// they have already been registered.
+    pub fn register_type<T: Traversable>(&mut self) {
+        if self.types.insert(TypeId::of::<T>()) {
+            T::register_casts(self);
+        }
+    }
+
+    /// Cast `node` to the trait object `D`, if a cast has been registered for its type.
+    pub fn cast<'a, D: ?Sized + 'static>(&self, node: &'a dyn Any) -> Option<&'a D> {
+        let caster = self.casts.get(&(TypeId::of::<D>(), node.type_id()))?;
+        let caster = caster.downcast_ref::<Caster<D>>()?;
+        caster(node)
+    }
+}
+
+/// A visitor that calls a closure on every node that can be cast to the trait object `D`.
+///
+/// The casts of each type are registered the first time a node of that type is entered.
+pub struct CastVisitor<D: ?Sized, B, F> {
+    registry: CastRegistry,
+    enter: F,
+    marker_dyn: PhantomData<fn(&D)>,
+    marker_break: PhantomData<B>,
+}
+
+impl<D: ?Sized, B, F> CastVisitor<D, B, F> {
+    /// Returns the registry populated by the traversal.
+    pub fn into_registry(self) -> CastRegistry {
+        self.registry
+    }
+}
+
+impl<D, B, F> Visitor for CastVisitor<D, B, F>
+where
+    D: ?Sized + 'static,
+    F: FnMut(&D) -> ControlFlow<B>,
+{
+    type Break = B;
+
+    const NODE_HOOKS: bool = true;
+
+    fn enter_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        self.registry.register_type::<T>();
+        if let Some(node) = self.registry.cast::<D>(node) {
+            (self.enter)(node)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<D: ?Sized, B, F> Finish for CastVisitor<D, B, F> {
+    type Output = ();
+
+    fn finish(self) -> Self::Output {}
+}
+
+/// Create a visitor that calls `enter` on every node that can be cast to the trait object `D`.
+pub fn make_cast_visitor<D, B, F>(enter: F) -> CastVisitor<D, B, F>
+where
+    D: ?Sized + 'static,
+    F: FnMut(&D) -> ControlFlow<B>,
+{
+    CastVisitor {
+        registry: CastRegistry::new(),
+        enter,
+        marker_dyn: PhantomData,
+        marker_break: PhantomData,
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Visitors whose hooks return `Result`s.
+
+use core::any::Any;
+use core::ops::ControlFlow;
+
+use crate::Edge;
+use crate::Finish;
+use crate::Traversable;
+use crate::TraversableMut;
+use crate::Visitor;
+use crate::VisitorMut;
+
+/// A visitor whose hooks return a `Result`, so that fallible passes can use the `?` operator.
+///
+/// The traversal policy, the error type, which is the `Break` type, and the edge and typed leaf
+/// hooks come from the [`Visitor`] implementation. When traversing with [`Fallible`], its
+/// [`Visitor::enter_node`] is called before [`TryVisitor::try_enter`], and its
+/// [`Visitor::leave_node`] after [`TryVisitor::try_leave`]. These only call its own `enter` and
+/// `leave` hooks by default, which do nothing unless implemented.
+pub trait TryVisitor: Visitor {
+    /// Called when the visitor is entering a node.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn try_enter(&mut self, this: &dyn Any) -> Result<(), Self::Break> {
+        let _ = this;
+        Ok(())
+    }
+
+    /// Called when the visitor is leaving a node.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn try_leave(&mut self, this: &dyn Any) -> Result<(), Self::Break> {
+        let _ = this;
+        Ok(())
+    }
+}
+
+/// A mutable visitor whose hooks return a `Result`.
+///
+/// See [`TryVisitor`].
+pub trait TryVisitorMut: VisitorMut {
+    /// Called when the visitor is entering a mutable node.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn try_enter_mut(&mut self, this: &mut dyn Any) -> Result<(), Self::Break> {
+        let _ = this;
+        Ok(())
+    }
+
+    /// Called when the visitor is leaving a mutable node.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn try_leave_mut(&mut self, this: &mut dyn Any) -> Result<(), Self::Break> {
+        let _ = this;
+        Ok(())
+    }
+}
+
+/// Adapts a [`TryVisitor`] or a [`TryVisitorMut`] to a visitor, breaking traversal with the first
+/// error returned by its hooks.
+pub struct Fallible<V> {
+    visitor: V,
+}
+
+impl<V> Fallible<V> {
+    /// Create an adapter for `visitor`.
+    pub fn new(visitor: V) -> Self {
+        Self { visitor }
+    }
+
+    /// Returns the wrapped visitor.
+    pub fn into_inner(self) -> V {
+        self.visitor
+    }
+}
+
+impl<V: Finish> Finish for Fallible<V> {
+    type Output = V::Output;
+
+    fn finish(self) -> Self::Output {
+        self.visitor.finish()
+    }
+}
+
+pub(crate) fn into_control_flow<E>(result: Result<(), E>) -> ControlFlow<E> {
+    match result {
+        Ok(()) => ControlFlow::Continue(()),
+        Err(error) => ControlFlow::Break(error),
+    }
+}
+
+impl<V: TryVisitor> Visitor for Fallible<V> {
+    type Break = V::Break;
+
+    const DETERMINISTIC_ORDER: bool = V::DETERMINISTIC_ORDER;
+    const VISIT_CONTAINERS: bool = V::VISIT_CONTAINERS;
+    const VISIT_TRIVIAL: bool = V::VISIT_TRIVIAL;
+    const VISIT_STD: bool = V::VISIT_STD;
+    const NODE_HOOKS: bool = V::NODE_HOOKS;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        into_control_flow(self.visitor.try_enter(this))
+    }
+
+    fn leave(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        into_control_flow(self.visitor.try_leave(this))
+    }
+
+    fn enter_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        self.visitor.enter_node(node)?;
+        self.enter(node)
+    }
+
+    fn leave_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        self.leave(node)?;
+        self.visitor.leave_node(node)
+    }
+
+    fn enter_edge(&mut self, edge: Edge) {
+        self.visitor.enter_edge(edge);
+    }
+
+    fn leave_edge(&mut self) {
+        self.visitor.leave_edge();
+    }
+
+    forward_leaf_hooks!();
+}
+
+impl<V: TryVisitorMut> VisitorMut for Fallible<V> {
+    type Break = V::Break;
+
+    const DETERMINISTIC_ORDER: bool = V::DETERMINISTIC_ORDER;
+    const VISIT_CONTAINERS: bool = V::VISIT_CONTAINERS;
+    const VISIT_TRIVIAL: bool = V::VISIT_TRIVIAL;
+    const VISIT_STD: bool = V::VISIT_STD;
+    const EDITS: bool = V::EDITS;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        into_control_flow(self.visitor.try_enter_mut(this))
+    }
+
+    fn leave_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        into_control_flow(self.visitor.try_leave_mut(this))
+    }
+
+    fn enter_node_mut<T: TraversableMut>(&mut self, node: &mut T) -> ControlFlow<Self::Break> {
+        self.visitor.enter_node_mut(node)?;
+        self.enter_mut(node)
+    }
+
+    fn leave_node_mut<T: TraversableMut>(&mut self, node: &mut T) -> ControlFlow<Self::Break> {
+        self.leave_mut(node)?;
+        self.visitor.leave_node_mut(node)
+    }
+
+    fn enter_edge_mut(&mut self, edge: Edge) {
+        self.visitor.enter_edge_mut(edge);
+    }
+
+    fn leave_edge_mut(&mut self) {
+        self.visitor.leave_edge_mut();
+    }
+
+    fn edit_mut(&mut self, this: &mut dyn Any) -> crate::Edit {
+        self.visitor.edit_mut(this)
+    }
+
+    forward_leaf_hooks_mut!();
+}
@@ -0,0 +1,198 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Visitors that carry per-node state from entering a node to leaving it.
+
+use core::any::Any;
+use core::ops::ControlFlow;
+use std::vec::Vec;
+
+use crate::Edge;
+use crate::Finish;
+use crate::Traversable;
+use crate::TraversableMut;
+use crate::Visitor;
+use crate::VisitorMut;
+
+/// A visitor whose entering hook returns a frame that is handed back when leaving the same node.
+///
+/// The traversal policy, the `Break` type, and the edge and typed leaf hooks come from the
+/// [`Visitor`] implementation. When traversing with [`Framed`], its [`Visitor::enter_node`] is
+/// called before entering the frame, and its [`Visitor::leave_node`] after leaving it. These only
+/// call its own `enter` and `leave` hooks by default, which do nothing unless implemented.
+pub trait FrameVisitor: Visitor {
+    /// The state carried from entering a node to leaving it.
+    type Frame;
+
+    /// Called when the visitor is entering a node, returning the frame for that node.
+    fn enter_frame(&mut self, this: &dyn Any) -> ControlFlow<Self::Break, Self::Frame>;
+
+    /// Called when the visitor is leaving a node, with the frame returned when entering it.
+    fn leave_frame(&mut self, this: &dyn Any, frame: Self::Frame) -> ControlFlow<Self::Break>;
+}
+
+/// A mutable visitor whose entering hook returns a frame that is handed back when leaving the same
+/// node.
+///
+/// See [`FrameVisitor`].
+pub trait FrameVisitorMut: VisitorMut {
+    /// The state carried from entering a node to leaving it.
+    type Frame;
+
+    /// Called when the visitor is entering a mutable node, returning the frame for that node.
+    fn enter_frame_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break, Self::Frame>;
+
+    /// Called when the visitor is leaving a mutable node, with the frame returned when entering it.
+    fn leave_frame_mut(
+        &mut self,
+        this: &mut dyn Any,
+        frame: Self::Frame,
+    ) -> ControlFlow<Self::Break>;
+}
+
+/// Adapts a [`FrameVisitor`] or a [`FrameVisitorMut`] to a visitor, keeping the frames of the
+/// nodes being traversed on a stack.
+///
+/// Since every entered node is left before its parent, the frames stay balanced. If the traversal
+/// breaks early, the frames of the nodes that were not left are dropped, so that the adapter can
+/// be reused.
+pub struct Framed<V, F> {
+    visitor: V,
+    frames: Vec<F>,
+}
+
+impl<V, F> Framed<V, F> {
+    /// Create an adapter for `visitor`.
+    pub fn new(visitor: V) -> Self {
+        Self {
+            visitor,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Returns the wrapped visitor.
+    pub fn into_inner(self) -> V {
+        self.visitor
+    }
+
+    // Drop the frames of the nodes that were not left if traversal breaks.
+    fn reset_on_break<B, C>(&mut self, flow: ControlFlow<B, C>) -> ControlFlow<B, C> {
+        if flow.is_break() {
+            self.frames.clear();
+        }
+        flow
+    }
+}
+
+impl<V: Finish, F> Finish for Framed<V, F> {
+    type Output = V::Output;
+
+    fn finish(self) -> Self::Output {
+        self.visitor.finish()
+    }
+}
+
+impl<V: FrameVisitor> Visitor for Framed<V, V::Frame> {
+    type Break = V::Break;
+
+    const DETERMINISTIC_ORDER: bool = V::DETERMINISTIC_ORDER;
+    const VISIT_CONTAINERS: bool = V::VISIT_CONTAINERS;
+    const VISIT_TRIVIAL: bool = V::VISIT_TRIVIAL;
+    const VISIT_STD: bool = V::VISIT_STD;
+    const NODE_HOOKS: bool = V::NODE_HOOKS;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        let frame = self.visitor.enter_frame(this);
+        let frame = self.reset_on_break(frame)?;
+        self.frames.push(frame);
+        ControlFlow::Continue(())
+    }
+
+    fn leave(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        let frame = self.frames.pop().expect("unbalanced frames");
+        let flow = self.visitor.leave_frame(this, frame);
+        self.reset_on_break(flow)
+    }
+
+    fn enter_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        let flow = self.visitor.enter_node(node);
+        self.reset_on_break(flow)?;
+        self.enter(node)
+    }
+
+    fn leave_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        self.leave(node)?;
+        let flow = self.visitor.leave_node(node);
+        self.reset_on_break(flow)
+    }
+
+    fn enter_edge(&mut self, edge: Edge) {
+        self.visitor.enter_edge(edge);
+    }
+
+    fn leave_edge(&mut self) {
+        self.visitor.leave_edge();
+    }
+
+    forward_leaf_hooks!(Self::reset_on_break);
+}
+
+impl<V: FrameVisitorMut> VisitorMut for Framed<V, <V as FrameVisitorMut>::Frame> {
+    type Break = <V as VisitorMut>::Break;
+
+    const DETERMINISTIC_ORDER: bool = <V as VisitorMut>::DETERMINISTIC_ORDER;
+    const VISIT_CONTAINERS: bool = <V as VisitorMut>::VISIT_CONTAINERS;
+    const VISIT_TRIVIAL: bool = <V as VisitorMut>::VISIT_TRIVIAL;
+    const VISIT_STD: bool = <V as VisitorMut>::VISIT_STD;
+    const EDITS: bool = V::EDITS;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        let frame = self.visitor.enter_frame_mut(this);
+        let frame = self.reset_on_break(frame)?;
+        self.frames.push(frame);
+        ControlFlow::Continue(())
+    }
+
+    fn leave_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        let frame = self.frames.pop().expect("unbalanced frames");
+        let flow = self.visitor.leave_frame_mut(this, frame);
+        self.reset_on_break(flow)
+    }
+
+    fn enter_node_mut<T: TraversableMut>(&mut self, node: &mut T) -> ControlFlow<Self::Break> {
+        let flow = self.visitor.enter_node_mut(node);
+        self.reset_on_break(flow)?;
+        self.enter_mut(node)
+    }
+
+    fn leave_node_mut<T: TraversableMut>(&mut self, node: &mut T) -> ControlFlow<Self::Break> {
+        self.leave_mut(node)?;
+        let flow = self.visitor.leave_node_mut(node);
+        self.reset_on_break(flow)
+    }
+
+    fn enter_edge_mut(&mut self, edge: Edge) {
+        self.visitor.enter_edge_mut(edge);
+    }
+
+    fn leave_edge_mut(&mut self) {
+        self.visitor.leave_edge_mut();
+    }
+
+    fn edit_mut(&mut self, this: &mut dyn Any) -> crate::Edit {
+        self.visitor.edit_mut(this)
+    }
+
+    forward_leaf_hooks_mut!(Self::reset_on_break);
+}
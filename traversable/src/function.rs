@@ -18,8 +18,20 @@ use core::any::Any;
 use core::marker::PhantomData;
 use core::ops::ControlFlow;
 
+use crate::Finish;
 use crate::Visitor;
 use crate::VisitorMut;
+use crate::fallible::into_control_flow;
+
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod path;
+
+#[cfg(feature = "std")]
+pub use self::builder::*;
+#[cfg(feature = "std")]
+pub use self::path::*;
 
 /// Type returned by `make_visitor` factories.
 pub struct FnVisitor<T, B, F1, F2> {
@@ -75,6 +87,128 @@ where
     }
 }
 
+impl<T, B, F1, F2> Finish for FnVisitor<T, B, F1, F2> {
+    type Output = ();
+
+    fn finish(self) -> Self::Output {}
+}
+
+/// Type returned by `make_fold_visitor` factories.
+pub struct FoldVisitor<T, A, B, F> {
+    acc: A,
+    enter: F,
+    marker_type: PhantomData<T>,
+    marker_break: PhantomData<B>,
+}
+
+impl<T, A, B, F> Visitor for FoldVisitor<T, A, B, F>
+where
+    T: Any,
+    F: FnMut(&mut A, &T) -> ControlFlow<B>,
+{
+    type Break = B;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_ref::<T>() {
+            (self.enter)(&mut self.acc, item)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<T, A, B, F> VisitorMut for FoldVisitor<T, A, B, F>
+where
+    T: Any,
+    F: FnMut(&mut A, &mut T) -> ControlFlow<B>,
+{
+    type Break = B;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_mut::<T>() {
+            (self.enter)(&mut self.acc, item)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<T, A, B, F> Finish for FoldVisitor<T, A, B, F> {
+    type Output = A;
+
+    fn finish(self) -> Self::Output {
+        self.acc
+    }
+}
+
+/// Type returned by `make_visitor_with_context` factories.
+pub struct ContextVisitor<T, C, B, F1, F2> {
+    context: C,
+    enter: F1,
+    leave: F2,
+    marker_type: PhantomData<T>,
+    marker_break: PhantomData<B>,
+}
+
+impl<T, C, B, F1, F2> ContextVisitor<T, C, B, F1, F2> {
+    /// Returns the context shared by the closures.
+    pub fn into_context(self) -> C {
+        self.context
+    }
+}
+
+impl<T, C, B, F1, F2> Visitor for ContextVisitor<T, C, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&mut C, &T) -> ControlFlow<B>,
+    F2: FnMut(&mut C, &T) -> ControlFlow<B>,
+{
+    type Break = B;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_ref::<T>() {
+            (self.enter)(&mut self.context, item)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn leave(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_ref::<T>() {
+            (self.leave)(&mut self.context, item)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<T, C, B, F1, F2> VisitorMut for ContextVisitor<T, C, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&mut C, &mut T) -> ControlFlow<B>,
+    F2: FnMut(&mut C, &mut T) -> ControlFlow<B>,
+{
+    type Break = B;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_mut::<T>() {
+            (self.enter)(&mut self.context, item)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn leave_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_mut::<T>() {
+            (self.leave)(&mut self.context, item)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<T, C, B, F1, F2> Finish for ContextVisitor<T, C, B, F1, F2> {
+    type Output = C;
+
+    fn finish(self) -> Self::Output {
+        self.context
+    }
+}
+
 type DefaultVisitFn<T, B> = fn(&T) -> ControlFlow<B>;
 type DefaultVisitFnMut<T, B> = fn(&mut T) -> ControlFlow<B>;
 
@@ -163,3 +297,124 @@ where
         marker_break: PhantomData,
     }
 }
+
+/// Similar to [`make_visitor`], but both closures receive the mutable `context`, which is the
+/// output of the visitor.
+pub fn make_visitor_with_context<T, C, B, F1, F2>(
+    context: C,
+    enter: F1,
+    leave: F2,
+) -> ContextVisitor<T, C, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&mut C, &T) -> ControlFlow<B>,
+    F2: FnMut(&mut C, &T) -> ControlFlow<B>,
+{
+    ContextVisitor {
+        context,
+        enter,
+        leave,
+        marker_type: PhantomData,
+        marker_break: PhantomData,
+    }
+}
+
+/// Similar to [`make_visitor_mut`], but both closures receive the mutable `context`, which is the
+/// output of the visitor.
+pub fn make_visitor_with_context_mut<T, C, B, F1, F2>(
+    context: C,
+    enter: F1,
+    leave: F2,
+) -> ContextVisitor<T, C, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&mut C, &mut T) -> ControlFlow<B>,
+    F2: FnMut(&mut C, &mut T) -> ControlFlow<B>,
+{
+    ContextVisitor {
+        context,
+        enter,
+        leave,
+        marker_type: PhantomData,
+        marker_break: PhantomData,
+    }
+}
+
+/// Create a visitor that folds items of a specific type into an accumulator on entering, which is
+/// the output of the visitor.
+pub fn make_fold_visitor<T, A, B, F>(init: A, enter: F) -> FoldVisitor<T, A, B, F>
+where
+    T: Any,
+    F: FnMut(&mut A, &T) -> ControlFlow<B>,
+{
+    FoldVisitor {
+        acc: init,
+        enter,
+        marker_type: PhantomData,
+        marker_break: PhantomData,
+    }
+}
+
+/// Similar to [`make_fold_visitor`], but for mutable items.
+pub fn make_fold_visitor_mut<T, A, B, F>(init: A, enter: F) -> FoldVisitor<T, A, B, F>
+where
+    T: Any,
+    F: FnMut(&mut A, &mut T) -> ControlFlow<B>,
+{
+    FoldVisitor {
+        acc: init,
+        enter,
+        marker_type: PhantomData,
+        marker_break: PhantomData,
+    }
+}
+
+/// Similar to [`make_visitor`], but the closures return a `Result`, and traversal breaks with the
+/// first error.
+pub fn make_try_visitor<T, E>(
+    mut enter: impl FnMut(&T) -> Result<(), E>,
+    mut leave: impl FnMut(&T) -> Result<(), E>,
+) -> impl Visitor<Break = E> + Finish
+where
+    T: Any,
+{
+    make_visitor(
+        move |item| into_control_flow(enter(item)),
+        move |item| into_control_flow(leave(item)),
+    )
+}
+
+/// Similar to [`make_try_visitor`], but the closure will only be called on entering.
+pub fn make_try_visitor_enter<T, E>(
+    mut enter: impl FnMut(&T) -> Result<(), E>,
+) -> impl Visitor<Break = E> + Finish
+where
+    T: Any,
+{
+    make_visitor_enter(move |item| into_control_flow(enter(item)))
+}
+
+/// Similar to [`make_visitor_mut`], but the closures return a `Result`, and traversal breaks with
+/// the first error.
+pub fn make_try_visitor_mut<T, E>(
+    mut enter: impl FnMut(&mut T) -> Result<(), E>,
+    mut leave: impl FnMut(&mut T) -> Result<(), E>,
+) -> impl VisitorMut<Break = E> + Finish
+where
+    T: Any,
+{
+    make_visitor_mut(
+        move |item| into_control_flow(enter(item)),
+        move |item| into_control_flow(leave(item)),
+    )
+}
+
+/// Similar to [`make_try_visitor_mut`], but the closure will only be called on entering.
+pub fn make_try_visitor_enter_mut<T, E>(
+    mut enter: impl FnMut(&mut T) -> Result<(), E>,
+) -> impl VisitorMut<Break = E> + Finish
+where
+    T: Any,
+{
+    make_visitor_enter_mut(move |item| into_control_flow(enter(item)))
+}
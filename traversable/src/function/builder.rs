@@ -0,0 +1,200 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::any::Any;
+use core::any::TypeId;
+use core::ops::ControlFlow;
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::Finish;
+use crate::Visitor;
+use crate::VisitorMut;
+
+type Handler<'a, B> = Box<dyn FnMut(&dyn Any) -> ControlFlow<B> + 'a>;
+type HandlerMut<'a, B> = Box<dyn FnMut(&mut dyn Any) -> ControlFlow<B> + 'a>;
+
+// Call the handlers registered for the type of `this`, in the order they were added.
+macro_rules! dispatch {
+    ( $handlers:expr, $this:ident $(, $mut:tt)? ) => {{
+        if let Some(handlers) = $handlers.get_mut(&(*$this).type_id()) {
+            for handler in handlers {
+                handler(&$($mut)? *$this)?;
+            }
+        }
+        ControlFlow::Continue(())
+    }};
+}
+
+/// Builder of a visitor that calls closures on items of different types, dispatching on their
+/// `TypeId`.
+///
+/// The built visitor has the default traversal policy of [`Visitor`]. Several closures can be
+/// registered for the same type, and are called in the order they were added.
+pub struct VisitorBuilder<'a, B> {
+    enter: BTreeMap<TypeId, Vec<Handler<'a, B>>>,
+    leave: BTreeMap<TypeId, Vec<Handler<'a, B>>>,
+}
+
+impl<B> Default for VisitorBuilder<'_, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, B> VisitorBuilder<'a, B> {
+    /// Create a builder without any closures.
+    pub fn new() -> Self {
+        Self {
+            enter: BTreeMap::new(),
+            leave: BTreeMap::new(),
+        }
+    }
+
+    /// Call `f` on entering items of type `T`.
+    pub fn on_enter<T: Any>(mut self, f: impl FnMut(&T) -> ControlFlow<B> + 'a) -> Self {
+        push_handler(&mut self.enter, f);
+        self
+    }
+
+    /// Call `f` on leaving items of type `T`.
+    pub fn on_leave<T: Any>(mut self, f: impl FnMut(&T) -> ControlFlow<B> + 'a) -> Self {
+        push_handler(&mut self.leave, f);
+        self
+    }
+
+    /// Build the visitor.
+    pub fn build(self) -> DispatchVisitor<'a, B> {
+        DispatchVisitor {
+            enter: self.enter,
+            leave: self.leave,
+        }
+    }
+}
+
+fn push_handler<'a, T: Any, B>(
+    handlers: &mut BTreeMap<TypeId, Vec<Handler<'a, B>>>,
+    mut f: impl FnMut(&T) -> ControlFlow<B> + 'a,
+) {
+    let handler: Handler<'a, B> = Box::new(move |this| match this.downcast_ref::<T>() {
+        Some(item) => f(item),
+        None => unreachable!("handlers are dispatched by type id"),
+    });
+    handlers.entry(TypeId::of::<T>()).or_default().push(handler);
+}
+
+/// Type returned by [`VisitorBuilder::build`].
+pub struct DispatchVisitor<'a, B> {
+    enter: BTreeMap<TypeId, Vec<Handler<'a, B>>>,
+    leave: BTreeMap<TypeId, Vec<Handler<'a, B>>>,
+}
+
+impl<B> Visitor for DispatchVisitor<'_, B> {
+    type Break = B;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        dispatch!(self.enter, this)
+    }
+
+    fn leave(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        dispatch!(self.leave, this)
+    }
+}
+
+impl<B> Finish for DispatchVisitor<'_, B> {
+    type Output = ();
+
+    fn finish(self) -> Self::Output {}
+}
+
+/// Builder of a visitor that calls closures on mutable items of different types, dispatching on
+/// their `TypeId`.
+///
+/// See [`VisitorBuilder`].
+pub struct VisitorMutBuilder<'a, B> {
+    enter: BTreeMap<TypeId, Vec<HandlerMut<'a, B>>>,
+    leave: BTreeMap<TypeId, Vec<HandlerMut<'a, B>>>,
+}
+
+impl<B> Default for VisitorMutBuilder<'_, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, B> VisitorMutBuilder<'a, B> {
+    /// Create a builder without any closures.
+    pub fn new() -> Self {
+        Self {
+            enter: BTreeMap::new(),
+            leave: BTreeMap::new(),
+        }
+    }
+
+    /// Call `f` on entering mutable items of type `T`.
+    pub fn on_enter<T: Any>(mut self, f: impl FnMut(&mut T) -> ControlFlow<B> + 'a) -> Self {
+        push_handler_mut(&mut self.enter, f);
+        self
+    }
+
+    /// Call `f` on leaving mutable items of type `T`.
+    pub fn on_leave<T: Any>(mut self, f: impl FnMut(&mut T) -> ControlFlow<B> + 'a) -> Self {
+        push_handler_mut(&mut self.leave, f);
+        self
+    }
+
+    /// Build the visitor.
+    pub fn build(self) -> DispatchVisitorMut<'a, B> {
+        DispatchVisitorMut {
+            enter: self.enter,
+            leave: self.leave,
+        }
+    }
+}
+
+fn push_handler_mut<'a, T: Any, B>(
+    handlers: &mut BTreeMap<TypeId, Vec<HandlerMut<'a, B>>>,
+    mut f: impl FnMut(&mut T) -> ControlFlow<B> + 'a,
+) {
+    let handler: HandlerMut<'a, B> = Box::new(move |this| match this.downcast_mut::<T>() {
+        Some(item) => f(item),
+        None => unreachable!("handlers are dispatched by type id"),
+    });
+    handlers.entry(TypeId::of::<T>()).or_default().push(handler);
+}
+
+/// Type returned by [`VisitorMutBuilder::build`].
+pub struct DispatchVisitorMut<'a, B> {
+    enter: BTreeMap<TypeId, Vec<HandlerMut<'a, B>>>,
+    leave: BTreeMap<TypeId, Vec<HandlerMut<'a, B>>>,
+}
+
+impl<B> VisitorMut for DispatchVisitorMut<'_, B> {
+    type Break = B;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        dispatch!(self.enter, this, mut)
+    }
+
+    fn leave_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        dispatch!(self.leave, this, mut)
+    }
+}
+
+impl<B> Finish for DispatchVisitorMut<'_, B> {
+    type Output = ();
+
+    fn finish(self) -> Self::Output {}
+}
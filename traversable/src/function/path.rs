@@ -0,0 +1,210 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::any::Any;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::ControlFlow;
+use std::vec::Vec;
+
+use crate::Edge;
+use crate::Finish;
+use crate::Visitor;
+use crate::VisitorMut;
+
+/// The location of a node during traversal, passed to the closures of `make_visitor_with_path`
+/// factories.
+///
+/// Displays as a chain of fields and indices from the root, e.g., `.body[2].name`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Path {
+    depth: usize,
+    edges: Vec<Edge>,
+}
+
+impl Path {
+    /// Returns the number of visited nodes enclosing the current node.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the edges from the root to the current node.
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    // Go back to the root once traversal breaks, since the nodes and edges entered so far are not
+    // left, so that the visitor can be reused for another traversal.
+    fn reset_on_break<B>(&mut self, flow: ControlFlow<B>) -> ControlFlow<B> {
+        if flow.is_break() {
+            self.depth = 0;
+            self.edges.clear();
+        }
+        flow
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for edge in &self.edges {
+            match edge {
+                Edge::Field(name) => write!(f, ".{name}")?,
+                Edge::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Type returned by `make_visitor_with_path` factories.
+pub struct PathVisitor<T, B, F1, F2> {
+    path: Path,
+    enter: F1,
+    leave: F2,
+    marker_type: PhantomData<T>,
+    marker_break: PhantomData<B>,
+}
+
+impl<T, B, F1, F2> Visitor for PathVisitor<T, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&T, &Path) -> ControlFlow<B>,
+    F2: FnMut(&T, &Path) -> ControlFlow<B>,
+{
+    type Break = B;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_ref::<T>() {
+            let flow = (self.enter)(item, &self.path);
+            self.path.reset_on_break(flow)?;
+        }
+        self.path.depth += 1;
+        ControlFlow::Continue(())
+    }
+
+    fn leave(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        self.path.depth -= 1;
+        if let Some(item) = this.downcast_ref::<T>() {
+            let flow = (self.leave)(item, &self.path);
+            self.path.reset_on_break(flow)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn enter_edge(&mut self, edge: Edge) {
+        self.path.edges.push(edge);
+    }
+
+    fn leave_edge(&mut self) {
+        self.path.edges.pop();
+    }
+}
+
+impl<T, B, F1, F2> VisitorMut for PathVisitor<T, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&mut T, &Path) -> ControlFlow<B>,
+    F2: FnMut(&mut T, &Path) -> ControlFlow<B>,
+{
+    type Break = B;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(item) = this.downcast_mut::<T>() {
+            let flow = (self.enter)(item, &self.path);
+            self.path.reset_on_break(flow)?;
+        }
+        self.path.depth += 1;
+        ControlFlow::Continue(())
+    }
+
+    fn leave_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        self.path.depth -= 1;
+        if let Some(item) = this.downcast_mut::<T>() {
+            let flow = (self.leave)(item, &self.path);
+            self.path.reset_on_break(flow)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn enter_edge_mut(&mut self, edge: Edge) {
+        self.path.edges.push(edge);
+    }
+
+    fn leave_edge_mut(&mut self) {
+        self.path.edges.pop();
+    }
+}
+
+impl<T, B, F1, F2> Finish for PathVisitor<T, B, F1, F2> {
+    type Output = ();
+
+    fn finish(self) -> Self::Output {}
+}
+
+type DefaultPathFn<T, B> = fn(&T, &Path) -> ControlFlow<B>;
+type DefaultPathFnMut<T, B> = fn(&mut T, &Path) -> ControlFlow<B>;
+
+/// Similar to [`make_visitor`](super::make_visitor), but the closures also receive the [`Path`]
+/// of the item.
+pub fn make_visitor_with_path<T, B, F1, F2>(enter: F1, leave: F2) -> PathVisitor<T, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&T, &Path) -> ControlFlow<B>,
+    F2: FnMut(&T, &Path) -> ControlFlow<B>,
+{
+    PathVisitor {
+        path: Path::default(),
+        enter,
+        leave,
+        marker_type: PhantomData,
+        marker_break: PhantomData,
+    }
+}
+
+/// Similar to [`make_visitor_with_path`], but the closure will only be called on entering.
+pub fn make_visitor_enter_with_path<T, B, F>(enter: F) -> PathVisitor<T, B, F, DefaultPathFn<T, B>>
+where
+    T: Any,
+    F: FnMut(&T, &Path) -> ControlFlow<B>,
+{
+    make_visitor_with_path(enter, |_, _| ControlFlow::Continue(()))
+}
+
+/// Similar to [`make_visitor_mut`](super::make_visitor_mut), but the closures also receive the
+/// [`Path`] of the item.
+pub fn make_visitor_with_path_mut<T, B, F1, F2>(enter: F1, leave: F2) -> PathVisitor<T, B, F1, F2>
+where
+    T: Any,
+    F1: FnMut(&mut T, &Path) -> ControlFlow<B>,
+    F2: FnMut(&mut T, &Path) -> ControlFlow<B>,
+{
+    PathVisitor {
+        path: Path::default(),
+        enter,
+        leave,
+        marker_type: PhantomData,
+        marker_break: PhantomData,
+    }
+}
+
+/// Similar to [`make_visitor_with_path_mut`], but the closure will only be called on entering.
+pub fn make_visitor_enter_with_path_mut<T, B, F>(
+    enter: F,
+) -> PathVisitor<T, B, F, DefaultPathFnMut<T, B>>
+where
+    T: Any,
+    F: FnMut(&mut T, &Path) -> ControlFlow<B>,
+{
+    make_visitor_with_path_mut(enter, |_, _| ControlFlow::Continue(()))
+}
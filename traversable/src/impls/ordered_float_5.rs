@@ -23,16 +23,16 @@ use crate::VisitorMut;
 
 impl<T: 'static> Traversable for OrderedFloat<T> {
     fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
-        visitor.enter(self)?;
-        visitor.leave(self)?;
+        visitor.enter_node(self)?;
+        visitor.leave_node(self)?;
         ControlFlow::Continue(())
     }
 }
 
 impl<T: 'static> TraversableMut for OrderedFloat<T> {
     fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
-        visitor.enter_mut(self)?;
-        visitor.leave_mut(self)?;
+        visitor.enter_node_mut(self)?;
+        visitor.leave_node_mut(self)?;
         ControlFlow::Continue(())
     }
 }
@@ -19,8 +19,11 @@ use stacksafe_1::stacksafe;
 
 use crate::Traversable;
 use crate::TraversableMut;
+use crate::Visits;
 
 impl<T: Traversable> Traversable for StackSafe<T> {
+    const VISITS: Visits = T::VISITS;
+
     #[stacksafe(crate = stacksafe_1)]
     fn traverse<V: crate::Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
         (**self).traverse(visitor)
@@ -28,6 +31,8 @@ impl<T: Traversable> Traversable for StackSafe<T> {
 }
 
 impl<T: TraversableMut> TraversableMut for StackSafe<T> {
+    const VISITS: Visits = T::VISITS;
+
     #[stacksafe(crate = stacksafe_1)]
     fn traverse_mut<V: crate::VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
         (**self).traverse_mut(visitor)
@@ -0,0 +1,70 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mutable traversal of the keys of maps.
+//!
+//! Maps only traverse their values mutably, since their keys cannot be mutated in place. The
+//! functions of this module also traverse the keys, by taking the entries out of the map, traversing
+//! them, and inserting them back. They can be used for fields of derived types with the `with_mut`
+//! attribute, e.g., `#[traverse(with_mut = "traversable::keys_mut::btree_map")]`.
+//!
+//! Entries are inserted back even if the traversal breaks early or panics. If the visitor makes two
+//! keys equal, only one of the entries is kept.
+
+use core::hash::BuildHasher;
+use core::hash::Hash;
+use core::ops::ControlFlow;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use crate::TraversableMut;
+use crate::VisitorMut;
+use crate::impl_std_container::traverse_container_mut;
+use crate::impl_std_container::traverse_entry_mut;
+use crate::impl_std_container::traverse_mut_and_reinsert;
+
+/// Traverse the keys and values of a `BTreeMap` mutably.
+pub fn btree_map<K, U, V>(map: &mut BTreeMap<K, U>, visitor: &mut V) -> ControlFlow<V::Break>
+where
+    K: TraversableMut + Ord,
+    U: TraversableMut,
+    V: VisitorMut,
+{
+    traverse_container_mut(map, visitor, K::VISITS.union(U::VISITS), |this, visitor| {
+        let entries = core::mem::take(this);
+        traverse_mut_and_reinsert(entries, visitor, traverse_entry_mut, |(key, value)| {
+            this.insert(key, value);
+        })
+    })
+}
+
+/// Traverse the keys and values of a `HashMap` mutably.
+///
+/// See [`sorted::hash_map_keys_mut`](crate::sorted::hash_map_keys_mut) to traverse the entries in
+/// a deterministic order.
+pub fn hash_map<K, U, S, V>(map: &mut HashMap<K, U, S>, visitor: &mut V) -> ControlFlow<V::Break>
+where
+    K: TraversableMut + Eq + Hash,
+    U: TraversableMut,
+    S: BuildHasher + 'static,
+    V: VisitorMut,
+{
+    traverse_container_mut(map, visitor, K::VISITS.union(U::VISITS), |this, visitor| {
+        let entries = this.drain().collect::<Vec<_>>();
+        traverse_mut_and_reinsert(entries, visitor, traverse_entry_mut, |(key, value)| {
+            this.insert(key, value);
+        })
+    })
+}
@@ -30,16 +30,168 @@ pub use traversable_derive::Traversable;
 /// See [`TraversableMut`].
 pub use traversable_derive::TraversableMut;
 
+// Forward the typed leaf hooks of `Visitor` to the `visitor` field of an adapter, passing their
+// result through `$wrap`, a function of the adapter and the result, if given.
+macro_rules! forward_leaf_hooks {
+    () => {
+        forward_leaf_hooks!(|_: &mut Self, flow| flow);
+    };
+    (@hooks $wrap:expr; $( $name:ident($type:ty); )+ ) => {
+        $(
+            fn $name(&mut self, value: $type) -> ControlFlow<Self::Break> {
+                let flow = self.visitor.$name(value);
+                ($wrap)(self, flow)
+            }
+        )+
+    };
+    ($wrap:expr) => {
+        forward_leaf_hooks! {
+            @hooks $wrap;
+            visit_bool(bool);
+            visit_i8(i8);
+            visit_i16(i16);
+            visit_i32(i32);
+            visit_i64(i64);
+            visit_i128(i128);
+            visit_u8(u8);
+            visit_u16(u16);
+            visit_u32(u32);
+            visit_u64(u64);
+            visit_u128(u128);
+            visit_f32(f32);
+            visit_f64(f64);
+            visit_char(char);
+            visit_str(&str);
+        }
+    };
+}
+
+// Forward the typed leaf hooks of `VisitorMut` to the `visitor` field of an adapter, like
+// `forward_leaf_hooks`.
+macro_rules! forward_leaf_hooks_mut {
+    () => {
+        forward_leaf_hooks_mut!(|_: &mut Self, flow| flow);
+    };
+    (@hooks $wrap:expr; $( $(#[$attr:meta])* $name:ident($type:ty); )+ ) => {
+        $(
+            $(#[$attr])*
+            fn $name(&mut self, value: &mut $type) -> ControlFlow<Self::Break> {
+                let flow = self.visitor.$name(value);
+                ($wrap)(self, flow)
+            }
+        )+
+    };
+    ($wrap:expr) => {
+        forward_leaf_hooks_mut! {
+            @hooks $wrap;
+            visit_bool_mut(bool);
+            visit_i8_mut(i8);
+            visit_i16_mut(i16);
+            visit_i32_mut(i32);
+            visit_i64_mut(i64);
+            visit_i128_mut(i128);
+            visit_isize_mut(isize);
+            visit_u8_mut(u8);
+            visit_u16_mut(u16);
+            visit_u32_mut(u32);
+            visit_u64_mut(u64);
+            visit_u128_mut(u128);
+            visit_usize_mut(usize);
+            visit_f32_mut(f32);
+            visit_f64_mut(f64);
+            visit_char_mut(char);
+            #[cfg(feature = "std")]
+            visit_string_mut(std::string::String);
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+pub mod cast;
+pub mod fallible;
+#[cfg(feature = "std")]
+pub mod frame;
 pub mod function;
+#[cfg(feature = "std")]
+pub mod keys_mut;
+pub mod read_only;
+pub mod schema;
+#[cfg(feature = "std")]
+pub mod sorted;
 
 /// Implementations for third-party library types.
 mod impls;
 
+// Declare typed leaf hooks of `Visitor` that forward to the hook of a wider type.
+macro_rules! forward_visit {
+    ( $( $(#[$attr:meta])* $name:ident($type:ty) => $wide:ident($wide_type:ty); )* ) => {
+        $(
+            $(#[$attr])*
+            #[inline]
+            fn $name(&mut self, value: $type) -> ControlFlow<Self::Break> {
+                self.$wide(<$wide_type>::from(value))
+            }
+        )*
+    };
+}
+
+// Declare typed leaf hooks of `VisitorMut` that do nothing by default.
+macro_rules! visit_mut {
+    ( $( $(#[$attr:meta])* $name:ident($type:ty); )* ) => {
+        $(
+            $(#[$attr])*
+            ///
+            /// Default implementation does nothing and continues traversal.
+            fn $name(&mut self, value: &mut $type) -> ControlFlow<Self::Break> {
+                let _ = value;
+                ControlFlow::Continue(())
+            }
+        )*
+    };
+}
+
 /// A visitor that can be used to traverse a data structure.
 pub trait Visitor {
     /// The type that can be used to break traversal early.
     type Break;
 
+    /// Whether hash-based collections, like `HashMap` and `HashSet`, are traversed in a
+    /// deterministic order.
+    ///
+    /// Hash-based collections are traversed in their iteration order, which depends on the random
+    /// state of their hasher and thus differs between runs. Collections traversed with the
+    /// functions of [`sorted`], which require their elements (keys for maps) to be `Ord`, are
+    /// instead traversed in ascending order of their elements when this is `true`, like `BTreeSet`
+    /// and `BTreeMap`. This costs an allocation and a sort per collection.
+    const DETERMINISTIC_ORDER: bool = false;
+
+    /// Whether containers, like `Vec`, `Option` or `HashMap`, are visited as nodes.
+    ///
+    /// By default, containers are transparent and only their elements are visited. When this is
+    /// `true`, the visitor also enters the container before its elements and leaves it after them.
+    /// Smart pointers and locks, like `Box`, `Arc` or `Mutex`, are always transparent.
+    const VISIT_CONTAINERS: bool = false;
+
+    /// Whether trivial types, like integers, floats, booleans, `char` and unit, are visited.
+    ///
+    /// Defaults to whether the `traverse-trivial` feature is enabled. Since cargo features are
+    /// unified across a build, visitors that rely on either behavior should set this explicitly.
+    const VISIT_TRIVIAL: bool = cfg!(feature = "traverse-trivial");
+
+    /// Whether primary standard library types, currently only `String`, are visited.
+    ///
+    /// Defaults to whether the `traverse-std` feature is enabled. Since cargo features are unified
+    /// across a build, visitors that rely on either behavior should set this explicitly.
+    const VISIT_STD: bool = cfg!(feature = "traverse-std");
+
+    /// Whether the visitor overrides [`Visitor::enter_node`] or [`Visitor::leave_node`].
+    ///
+    /// Visitors that override the node hooks must set this, so that adapters that cannot call them,
+    /// like [`ReadOnly`](read_only::ReadOnly), reject the visitor at compile time instead of
+    /// silently skipping its hooks. Adapters that call the node hooks of the visitor they wrap
+    /// forward this policy.
+    const NODE_HOOKS: bool = false;
+
     /// Called when the visitor is entering a node.
     ///
     /// Default implementation does nothing and continues traversal.
@@ -55,6 +207,149 @@ pub trait Visitor {
         let _ = this;
         ControlFlow::Continue(())
     }
+
+    /// Called when the visitor is entering a node, with the static type of the node.
+    ///
+    /// Traversal always calls this method, rather than [`Visitor::enter`], so visitors can
+    /// override it to dispatch on `T` statically, e.g., by comparing `TypeId::of::<T>()` to the
+    /// types of interest, which the compiler folds away for every `T`. Visitors that override it
+    /// must set [`Visitor::NODE_HOOKS`].
+    ///
+    /// Default implementation calls [`Visitor::enter`].
+    #[inline]
+    fn enter_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        self.enter(node)
+    }
+
+    /// Called when the visitor is leaving a node, with the static type of the node.
+    ///
+    /// See [`Visitor::enter_node`]. Default implementation calls [`Visitor::leave`].
+    #[inline]
+    fn leave_node<T: Traversable>(&mut self, node: &T) -> ControlFlow<Self::Break> {
+        self.leave(node)
+    }
+
+    /// Called before traversing the child of a node through the given edge.
+    ///
+    /// Edges are reported for the fields of derived types, the elements of tuples, and the
+    /// elements of arrays, slices, `Vec`, `VecDeque` and `LinkedList`. Other containers and smart
+    /// pointers are transparent. Together with [`Visitor::leave_edge`], this lets a visitor track
+    /// the path from the root to the current node.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn enter_edge(&mut self, edge: Edge) {
+        let _ = edge;
+    }
+
+    /// Called after traversing the child of a node through the last entered edge, unless
+    /// traversal breaks.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn leave_edge(&mut self) {}
+
+    /// Called with the value of a `bool` between entering and leaving it.
+    ///
+    /// Like the other typed leaf hooks, this is only called if [`Visitor::VISIT_TRIVIAL`] is set,
+    /// or [`Visitor::VISIT_STD`] for [`Visitor::visit_str`].
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_bool(&mut self, value: bool) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the value of an `i64` or `isize` between entering and leaving it.
+    ///
+    /// Narrower signed integers are passed to this hook by default, see [`Visitor::visit_i8`].
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_i64(&mut self, value: i64) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the value of an `i128` between entering and leaving it.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_i128(&mut self, value: i128) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the value of a `u64` or `usize` between entering and leaving it.
+    ///
+    /// Narrower unsigned integers are passed to this hook by default, see [`Visitor::visit_u8`].
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_u64(&mut self, value: u64) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the value of a `u128` between entering and leaving it.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_u128(&mut self, value: u128) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the value of an `f64` between entering and leaving it.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_f64(&mut self, value: f64) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the value of a `char` between entering and leaving it.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_char(&mut self, value: char) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the contents of a `String` between entering and leaving it.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    fn visit_str(&mut self, value: &str) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    forward_visit! {
+        /// Called with the value of an `i8` between entering and leaving it.
+        ///
+        /// Default implementation calls [`Visitor::visit_i64`].
+        visit_i8(i8) => visit_i64(i64);
+        /// Called with the value of an `i16` between entering and leaving it.
+        ///
+        /// Default implementation calls [`Visitor::visit_i64`].
+        visit_i16(i16) => visit_i64(i64);
+        /// Called with the value of an `i32` between entering and leaving it.
+        ///
+        /// Default implementation calls [`Visitor::visit_i64`].
+        visit_i32(i32) => visit_i64(i64);
+        /// Called with the value of a `u8` between entering and leaving it.
+        ///
+        /// Default implementation calls [`Visitor::visit_u64`].
+        visit_u8(u8) => visit_u64(u64);
+        /// Called with the value of a `u16` between entering and leaving it.
+        ///
+        /// Default implementation calls [`Visitor::visit_u64`].
+        visit_u16(u16) => visit_u64(u64);
+        /// Called with the value of a `u32` between entering and leaving it.
+        ///
+        /// Default implementation calls [`Visitor::visit_u64`].
+        visit_u32(u32) => visit_u64(u64);
+        /// Called with the value of an `f32` between entering and leaving it.
+        ///
+        /// Default implementation calls [`Visitor::visit_f64`].
+        visit_f32(f32) => visit_f64(f64);
+    }
 }
 
 /// A visitor that can be used to traverse a mutable data structure.
@@ -62,6 +357,34 @@ pub trait VisitorMut {
     /// The type that can be used to break traversal early.
     type Break;
 
+    /// Whether hash-based collections are traversed in a deterministic order.
+    ///
+    /// See [`Visitor::DETERMINISTIC_ORDER`].
+    const DETERMINISTIC_ORDER: bool = false;
+
+    /// Whether containers are visited as nodes.
+    ///
+    /// See [`Visitor::VISIT_CONTAINERS`]. Since the container is entered before its elements are
+    /// traversed, elements pushed to it on entering are traversed as well.
+    const VISIT_CONTAINERS: bool = false;
+
+    /// Whether trivial types are visited.
+    ///
+    /// See [`Visitor::VISIT_TRIVIAL`].
+    const VISIT_TRIVIAL: bool = cfg!(feature = "traverse-trivial");
+
+    /// Whether primary standard library types are visited.
+    ///
+    /// See [`Visitor::VISIT_STD`].
+    const VISIT_STD: bool = cfg!(feature = "traverse-std");
+
+    /// Whether containers ask the visitor for edits, see [`VisitorMut::edit_mut`].
+    ///
+    /// Defaults to `false`, in which case [`VisitorMut::edit_mut`] is never called. Since any
+    /// element may be edited, containers do not skip their elements when this is `true`, see
+    /// [`Visits::may_call_mut`].
+    const EDITS: bool = false;
+
     /// Called when the visitor is entering a mutable node.
     ///
     /// Default implementation does nothing and continues traversal.
@@ -77,54 +400,376 @@ pub trait VisitorMut {
         let _ = this;
         ControlFlow::Continue(())
     }
+
+    /// Called when the visitor is entering a mutable node, with the static type of the node.
+    ///
+    /// See [`Visitor::enter_node`]. Default implementation calls [`VisitorMut::enter_mut`].
+    #[inline]
+    fn enter_node_mut<T: TraversableMut>(&mut self, node: &mut T) -> ControlFlow<Self::Break> {
+        self.enter_mut(node)
+    }
+
+    /// Called when the visitor is leaving a mutable node, with the static type of the node.
+    ///
+    /// See [`Visitor::enter_node`]. Default implementation calls [`VisitorMut::leave_mut`].
+    #[inline]
+    fn leave_node_mut<T: TraversableMut>(&mut self, node: &mut T) -> ControlFlow<Self::Break> {
+        self.leave_mut(node)
+    }
+
+    /// Called before traversing the child of a mutable node through the given edge.
+    ///
+    /// See [`Visitor::enter_edge`]. Default implementation does nothing.
+    #[inline]
+    fn enter_edge_mut(&mut self, edge: Edge) {
+        let _ = edge;
+    }
+
+    /// Called after traversing the child of a mutable node through the last entered edge, unless
+    /// traversal breaks.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn leave_edge_mut(&mut self) {}
+
+    visit_mut! {
+        /// Called with a mutable `bool` between entering and leaving it.
+        ///
+        /// Unlike the hooks of [`Visitor`], every type has its own mutable hook, which is only
+        /// called if [`VisitorMut::VISIT_TRIVIAL`] is set, or [`VisitorMut::VISIT_STD`] for
+        /// [`VisitorMut::visit_string_mut`].
+        visit_bool_mut(bool);
+        /// Called with a mutable `i8` between entering and leaving it.
+        visit_i8_mut(i8);
+        /// Called with a mutable `i16` between entering and leaving it.
+        visit_i16_mut(i16);
+        /// Called with a mutable `i32` between entering and leaving it.
+        visit_i32_mut(i32);
+        /// Called with a mutable `i64` between entering and leaving it.
+        visit_i64_mut(i64);
+        /// Called with a mutable `i128` between entering and leaving it.
+        visit_i128_mut(i128);
+        /// Called with a mutable `isize` between entering and leaving it.
+        visit_isize_mut(isize);
+        /// Called with a mutable `u8` between entering and leaving it.
+        visit_u8_mut(u8);
+        /// Called with a mutable `u16` between entering and leaving it.
+        visit_u16_mut(u16);
+        /// Called with a mutable `u32` between entering and leaving it.
+        visit_u32_mut(u32);
+        /// Called with a mutable `u64` between entering and leaving it.
+        visit_u64_mut(u64);
+        /// Called with a mutable `u128` between entering and leaving it.
+        visit_u128_mut(u128);
+        /// Called with a mutable `usize` between entering and leaving it.
+        visit_usize_mut(usize);
+        /// Called with a mutable `f32` between entering and leaving it.
+        visit_f32_mut(f32);
+        /// Called with a mutable `f64` between entering and leaving it.
+        visit_f64_mut(f64);
+        /// Called with a mutable `char` between entering and leaving it.
+        visit_char_mut(char);
+    }
+
+    /// Called with a mutable `String` between entering and leaving it.
+    ///
+    /// Default implementation does nothing and continues traversal.
+    #[cfg(feature = "std")]
+    fn visit_string_mut(&mut self, value: &mut std::string::String) -> ControlFlow<Self::Break> {
+        let _ = value;
+        ControlFlow::Continue(())
+    }
+
+    /// Called by a container after one of its elements has been traversed, to edit the container
+    /// at that element.
+    ///
+    /// This is called for the elements of `Vec`, `VecDeque` and `Option`, and for the values of
+    /// `BTreeMap` and `HashMap`, only if [`VisitorMut::EDITS`] is set. Elements inserted or replaced
+    /// by an edit are not traversed, and edits that the container does not support are ignored,
+    /// see [`Edit`].
+    ///
+    /// Default implementation keeps the element.
+    fn edit_mut(&mut self, this: &mut dyn core::any::Any) -> Edit {
+        let _ = this;
+        Edit::Keep
+    }
+}
+
+/// An edge from a node to one of its children, see [`Visitor::enter_edge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// A named field of a struct or an enum variant.
+    Field(&'static str),
+    /// An unnamed field of a struct or an enum variant, or an element of a tuple or a sequence.
+    Index(usize),
+}
+
+/// A visitor that produces an output once traversal is done, see [`Traversable::visit_with`].
+pub trait Finish {
+    /// The type of the output.
+    type Output;
+
+    /// Consume the visitor after a traversal that did not break, producing its output.
+    fn finish(self) -> Self::Output;
+}
+
+/// A structural edit of a container at one of its elements, see [`VisitorMut::edit_mut`].
+///
+/// Containers ignore the edits they do not support, as well as edits carrying a value that does
+/// not have the type of their elements, and keep the element instead. Edits carrying a value
+/// require the `std` feature.
+pub enum Edit {
+    /// Keep the element.
+    Keep,
+    /// Remove the element from the container.
+    Remove,
+    /// Replace the element with the given value.
+    #[cfg(feature = "std")]
+    Replace(std::boxed::Box<dyn core::any::Any>),
+    /// Insert the given value before the element.
+    ///
+    /// Not supported by `Option` and maps.
+    #[cfg(feature = "std")]
+    InsertBefore(std::boxed::Box<dyn core::any::Any>),
+    /// Insert the given value after the element.
+    ///
+    /// Not supported by `Option` and maps.
+    #[cfg(feature = "std")]
+    InsertAfter(std::boxed::Box<dyn core::any::Any>),
+}
+
+#[cfg(feature = "std")]
+impl Edit {
+    /// Create an edit that replaces the element with `value`.
+    pub fn replace<T: core::any::Any>(value: T) -> Self {
+        Self::Replace(std::boxed::Box::new(value))
+    }
+
+    /// Create an edit that inserts `value` before the element.
+    pub fn insert_before<T: core::any::Any>(value: T) -> Self {
+        Self::InsertBefore(std::boxed::Box::new(value))
+    }
+
+    /// Create an edit that inserts `value` after the element.
+    pub fn insert_after<T: core::any::Any>(value: T) -> Self {
+        Self::InsertAfter(std::boxed::Box::new(value))
+    }
 }
 
 /// A trait for types that can be traversed by a visitor.
 pub trait Traversable: core::any::Any {
+    /// The kinds of nodes that traversing this type may visit.
+    ///
+    /// Containers skip traversing their elements entirely if the elements never call the visitor,
+    /// e.g., a `Vec<u8>` for a visitor that does not visit trivial types.
+    ///
+    /// Defaults to [`Visits::ANY`], which is always correct, but prevents such skipping. Derived
+    /// implementations of types that are visited themselves always use the default. Types that
+    /// are not, with `#[traverse(skip)]`, can compute it from the types of their traversed fields
+    /// with `#[traverse(skip, visits = "fields")]`, unless these types refer back to the deriving
+    /// type through another type that does the same, which the compiler reports as a cycle.
+    const VISITS: Visits = Visits::ANY;
+
+    /// The fields and variants of this type, see [`schema`].
+    ///
+    /// Defaults to `None`. Derived implementations provide the schema of the deriving type.
+    const SCHEMA: Option<&'static schema::TypeSchema> = None;
+
     /// Traverse the data structure with the given visitor.
     fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break>;
+
+    /// Register the casts of this type to trait objects, see [`cast::CastRegistry`].
+    ///
+    /// Default implementation registers nothing.
+    #[cfg(feature = "std")]
+    fn register_casts(registry: &mut cast::CastRegistry) {
+        let _ = registry;
+    }
+
+    /// Traverse the data structure with the given visitor, returning the value it broke
+    /// traversal with as an error.
+    fn try_traverse<V: Visitor>(&self, visitor: &mut V) -> Result<(), V::Break> {
+        match self.traverse(visitor) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(b) => Err(b),
+        }
+    }
+
+    /// Traverse the data structure with the given visitor and finish it.
+    ///
+    /// Returns the output of the visitor, or the value it broke traversal with.
+    fn visit_with<V: Visitor + Finish>(&self, mut visitor: V) -> Result<V::Output, V::Break> {
+        self.try_traverse(&mut visitor)?;
+        Ok(visitor.finish())
+    }
 }
 
 /// A trait for types that can be traversed mutably by a visitor.
+///
+/// Mutable traversal visits the same nodes as [`Traversable::traverse`], except for the keys of
+/// maps: `BTreeMap` and `HashMap` only traverse their values mutably, since their keys cannot be
+/// mutated in place. Their keys are traversed mutably with the functions of `keys_mut`, e.g., for
+/// fields of derived types with `#[traverse(with_mut = "traversable::keys_mut::btree_map")]`.
 pub trait TraversableMut: core::any::Any {
+    /// The kinds of nodes that traversing this type mutably may visit.
+    ///
+    /// See [`Traversable::VISITS`].
+    const VISITS: Visits = Visits::ANY;
+
     /// Traverse the mutable data structure with the given visitor.
     fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break>;
+
+    /// Traverse the mutable data structure with the given visitor, returning the value it broke
+    /// traversal with as an error.
+    fn try_traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Break> {
+        match self.traverse_mut(visitor) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(b) => Err(b),
+        }
+    }
+
+    /// Traverse the mutable data structure with the given visitor and finish it.
+    ///
+    /// See [`Traversable::visit_with`].
+    fn visit_with_mut<V: VisitorMut + Finish>(
+        &mut self,
+        mut visitor: V,
+    ) -> Result<V::Output, V::Break> {
+        self.try_traverse_mut(&mut visitor)?;
+        Ok(visitor.finish())
+    }
 }
 
-#[allow(unused_macros)]
-macro_rules! blank_traverse_impl {
-    ( $type:ty ) => {
-        impl Traversable for $type {
-            #[inline]
-            fn traverse<V: Visitor>(&self, _visitor: &mut V) -> ControlFlow<V::Break> {
-                ControlFlow::Continue(())
-            }
+/// The kinds of nodes that traversing a type may visit, see [`Traversable::VISITS`].
+///
+/// Kinds that are visited depending on the visitor's policy are tracked separately, so that
+/// [`Visits::may_call`] can tell whether a given visitor is called at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Visits(u8);
+
+impl Visits {
+    /// Never calls the visitor.
+    pub const NOTHING: Self = Self(0);
+
+    /// Visits trivial types, if the visitor sets [`Visitor::VISIT_TRIVIAL`].
+    pub const TRIVIAL: Self = Self(1 << 0);
+
+    /// Visits primary standard library types, if the visitor sets [`Visitor::VISIT_STD`].
+    pub const STD: Self = Self(1 << 1);
+
+    /// Visits containers, if the visitor sets [`Visitor::VISIT_CONTAINERS`].
+    pub const CONTAINERS: Self = Self(1 << 2);
+
+    /// May visit any node, regardless of the visitor's policy.
+    pub const ANY: Self = Self(u8::MAX);
+
+    /// Returns the kinds of nodes visited by either `self` or `other`.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns whether traversing may call the visitor `V`.
+    pub const fn may_call<V: Visitor>(self) -> bool {
+        self.may_call_with(V::VISIT_TRIVIAL, V::VISIT_STD, V::VISIT_CONTAINERS)
+    }
+
+    /// Returns whether traversing mutably may call the visitor `V`.
+    ///
+    /// This is always `true` if `V` sets [`VisitorMut::EDITS`], since containers may ask it for an
+    /// edit at any element.
+    pub const fn may_call_mut<V: VisitorMut>(self) -> bool {
+        V::EDITS || self.may_call_with(V::VISIT_TRIVIAL, V::VISIT_STD, V::VISIT_CONTAINERS)
+    }
+
+    const fn may_call_with(self, trivial: bool, std: bool, containers: bool) -> bool {
+        let mut policy = Self::ANY.0 & !(Self::TRIVIAL.0 | Self::STD.0 | Self::CONTAINERS.0);
+        if trivial {
+            policy |= Self::TRIVIAL.0;
+        }
+        if std {
+            policy |= Self::STD.0;
         }
+        if containers {
+            policy |= Self::CONTAINERS.0;
+        }
+        self.0 & policy != 0
+    }
+}
 
-        impl TraversableMut for $type {
-            #[inline]
-            fn traverse_mut<V: VisitorMut>(&mut self, _visitor: &mut V) -> ControlFlow<V::Break> {
-                ControlFlow::Continue(())
-            }
+/// Downcast a node to several types and match patterns against it in one construct.
+///
+/// `match_node!(this, pattern => expr, ..., _ => expr)` takes a `&dyn Any`, or a `&mut dyn Any`
+/// which is reborrowed immutably, and evaluates the expression of the first arm whose pattern
+/// matches. The type to downcast to is inferred from each pattern, e.g., `Literal::Float(f)`
+/// downcasts to `Literal`, so arms for different types can be mixed. Arms may have `if` guards,
+/// and the last arm must be the wildcard `_`, which is evaluated if no other arm matches. Like in
+/// `match`, arms whose body is a block do not need a trailing comma.
+///
+/// `match_node!(mut this, ...)` takes a `&mut dyn Any` and binds mutable references instead.
+#[macro_export]
+macro_rules! match_node {
+    (@arm $downcast:ident, $this:ident; _ => $default:expr $(,)?) => {
+        $default
+    };
+    (@arm $downcast:ident, $this:ident;
+        $pat:pat $(if $guard:expr)? => $body:expr, $($rest:tt)*
+    ) => {
+        match $this.$downcast() {
+            ::core::option::Option::Some($pat) $(if $guard)? => $body,
+            _ => $crate::match_node!(@arm $downcast, $this; $($rest)*),
+        }
+    };
+    (@arm $downcast:ident, $this:ident;
+        $pat:pat $(if $guard:expr)? => $body:block $($rest:tt)+
+    ) => {
+        match $this.$downcast() {
+            ::core::option::Option::Some($pat) $(if $guard)? => $body,
+            _ => $crate::match_node!(@arm $downcast, $this; $($rest)+),
         }
     };
+    (mut $this:expr, $($arms:tt)*) => {{
+        let this: &mut dyn ::core::any::Any = $this;
+        $crate::match_node!(@arm downcast_mut, this; $($arms)*)
+    }};
+    ($this:expr, $($arms:tt)*) => {{
+        let this: &dyn ::core::any::Any = $this;
+        $crate::match_node!(@arm downcast_ref, this; $($arms)*)
+    }};
 }
 
-#[allow(unused_macros)]
+// Implement Traversal for leaf types, which are only visited if the visitor's `$policy` is set.
+// Between entering and leaving, the value is passed to the given typed hooks.
 macro_rules! trivial_traverse_impl {
-    ( $type:ty ) => {
+    (
+        $type:ty, $policy:ident, $visits:ident,
+        |$visitor:ident, $this:ident| $visit:expr, $visit_mut:expr
+    ) => {
         impl Traversable for $type {
+            const VISITS: Visits = Visits::$visits;
+
+            #[inline]
             fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
-                visitor.enter(self)?;
-                visitor.leave(self)?;
+                if V::$policy {
+                    visitor.enter_node(self)?;
+                    let ($visitor, $this) = (&mut *visitor, self);
+                    $visit?;
+                    visitor.leave_node(self)?;
+                }
                 ControlFlow::Continue(())
             }
         }
 
         impl TraversableMut for $type {
+            const VISITS: Visits = Visits::$visits;
+
+            #[inline]
             fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
-                visitor.enter_mut(self)?;
-                visitor.leave_mut(self)?;
+                if V::$policy {
+                    visitor.enter_node_mut(self)?;
+                    let ($visitor, $this) = (&mut *visitor, &mut *self);
+                    $visit_mut?;
+                    visitor.leave_node_mut(self)?;
+                }
                 ControlFlow::Continue(())
             }
         }
@@ -134,41 +779,45 @@ macro_rules! trivial_traverse_impl {
 mod impl_trivial {
     use super::*;
 
-    #[cfg(not(feature = "traverse-trivial"))]
-    macro_rules! trivial_impl {
-        ( $type:ty ) => {
-            blank_traverse_impl!($type);
-        };
-    }
-
-    #[cfg(feature = "traverse-trivial")]
     macro_rules! trivial_impl {
-        ( $type:ty ) => {
-            trivial_traverse_impl!($type);
+        ( $type:ty, $visit:ident, $visit_mut:ident ) => {
+            trivial_traverse_impl!(
+                $type,
+                VISIT_TRIVIAL,
+                TRIVIAL,
+                |visitor, this| visitor.$visit(*this as _),
+                visitor.$visit_mut(this)
+            );
         };
     }
 
-    trivial_impl!(());
+    trivial_traverse_impl!(
+        (),
+        VISIT_TRIVIAL,
+        TRIVIAL,
+        |_visitor, _this| ControlFlow::<V::Break>::Continue(()),
+        ControlFlow::<V::Break>::Continue(())
+    );
 
-    trivial_impl!(u8);
-    trivial_impl!(u16);
-    trivial_impl!(u32);
-    trivial_impl!(u64);
-    trivial_impl!(u128);
-    trivial_impl!(usize);
+    trivial_impl!(u8, visit_u8, visit_u8_mut);
+    trivial_impl!(u16, visit_u16, visit_u16_mut);
+    trivial_impl!(u32, visit_u32, visit_u32_mut);
+    trivial_impl!(u64, visit_u64, visit_u64_mut);
+    trivial_impl!(u128, visit_u128, visit_u128_mut);
+    trivial_impl!(usize, visit_u64, visit_usize_mut);
 
-    trivial_impl!(i8);
-    trivial_impl!(i16);
-    trivial_impl!(i32);
-    trivial_impl!(i64);
-    trivial_impl!(i128);
-    trivial_impl!(isize);
+    trivial_impl!(i8, visit_i8, visit_i8_mut);
+    trivial_impl!(i16, visit_i16, visit_i16_mut);
+    trivial_impl!(i32, visit_i32, visit_i32_mut);
+    trivial_impl!(i64, visit_i64, visit_i64_mut);
+    trivial_impl!(i128, visit_i128, visit_i128_mut);
+    trivial_impl!(isize, visit_i64, visit_isize_mut);
 
-    trivial_impl!(f32);
-    trivial_impl!(f64);
+    trivial_impl!(f32, visit_f32, visit_f32_mut);
+    trivial_impl!(f64, visit_f64, visit_f64_mut);
 
-    trivial_impl!(char);
-    trivial_impl!(bool);
+    trivial_impl!(char, visit_char, visit_char_mut);
+    trivial_impl!(bool, visit_bool, visit_bool_mut);
 }
 
 mod impl_tuple {
@@ -183,9 +832,13 @@ mod impl_tuple {
                         $type: Traversable
                     ),+
                 {
+                    const VISITS: Visits = Visits::NOTHING $( .union(<$type as Traversable>::VISITS) )+;
+
                     fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
                         $(
+                            visitor.enter_edge(Edge::Index($field));
                             self.$field.traverse(visitor)?;
+                            visitor.leave_edge();
                         )+
                         ControlFlow::Continue(())
                     }
@@ -197,9 +850,14 @@ mod impl_tuple {
                         $type: TraversableMut
                     ),+
                 {
+                    const VISITS: Visits =
+                        Visits::NOTHING $( .union(<$type as TraversableMut>::VISITS) )+;
+
                     fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
                         $(
+                            visitor.enter_edge_mut(Edge::Index($field));
                             self.$field.traverse_mut(visitor)?;
+                            visitor.leave_edge_mut();
                         )+
                         ControlFlow::Continue(())
                     }
@@ -230,50 +888,58 @@ mod impl_std_primary {
 
     use super::*;
 
-    #[cfg(not(feature = "traverse-std"))]
-    macro_rules! std_primary_impl {
-        ( $type:ty ) => {
-            blank_traverse_impl!($type);
-        };
-    }
-
-    #[cfg(feature = "traverse-std")]
-    macro_rules! std_primary_impl {
-        ( $type:ty ) => {
-            trivial_traverse_impl!($type);
-        };
-    }
-
-    std_primary_impl!(String);
+    trivial_traverse_impl!(
+        String,
+        VISIT_STD,
+        STD,
+        |visitor, this| visitor.visit_str(this),
+        visitor.visit_string_mut(this)
+    );
 }
 
 #[cfg(feature = "std")]
 mod impl_std_container {
+    use core::any::Any;
+    use core::hash::BuildHasher;
+    use core::hash::Hash;
     use std::boxed::Box;
     use std::cell::Cell;
+    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
+    use std::collections::BinaryHeap;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::collections::VecDeque;
     use std::sync::Arc;
     use std::sync::Mutex;
     use std::sync::RwLock;
+    use std::vec::Vec;
 
     use super::*;
 
     // Helper traits to the generic `IntoIterator` Traversable impl
     trait DerefAndTraverse {
+        const VISITS: Visits;
         fn deref_and_traverse<V: Visitor>(self, visitor: &mut V) -> ControlFlow<V::Break>;
     }
 
     trait DerefAndTraverseMut {
+        const VISITS: Visits;
         fn deref_and_traverse_mut<V: VisitorMut>(self, visitor: &mut V) -> ControlFlow<V::Break>;
     }
 
     // Most collections iterate over item references, this is the trait impl that handles that case
     impl<T: Traversable> DerefAndTraverse for &T {
+        const VISITS: Visits = T::VISITS;
+
         fn deref_and_traverse<V: Visitor>(self, visitor: &mut V) -> ControlFlow<V::Break> {
             self.traverse(visitor)
         }
     }
 
     impl<T: TraversableMut> DerefAndTraverseMut for &mut T {
+        const VISITS: Visits = T::VISITS;
+
         fn deref_and_traverse_mut<V: VisitorMut>(self, visitor: &mut V) -> ControlFlow<V::Break> {
             self.traverse_mut(visitor)
         }
@@ -281,6 +947,8 @@ mod impl_std_container {
 
     // Map-like collections iterate over item references pairs
     impl<TK: Traversable, TV: Traversable> DerefAndTraverse for (&TK, &TV) {
+        const VISITS: Visits = TK::VISITS.union(TV::VISITS);
+
         fn deref_and_traverse<V: Visitor>(self, visitor: &mut V) -> ControlFlow<V::Break> {
             self.0.traverse(visitor)?;
             self.1.traverse(visitor)?;
@@ -288,30 +956,96 @@ mod impl_std_container {
         }
     }
 
-    // Map-like collections have mutable iterators that allow mutating only the value, not the key
-    impl<TK, TV: TraversableMut> DerefAndTraverseMut for (TK, &mut TV) {
-        fn deref_and_traverse_mut<V: VisitorMut>(self, visitor: &mut V) -> ControlFlow<V::Break> {
-            self.1.traverse_mut(visitor)
+    // Traverse the elements of a container, visiting the container itself around them if the
+    // visitor opts in with `VISIT_CONTAINERS`. The elements are skipped entirely if they never call
+    // the visitor, according to `items`.
+    pub(crate) fn traverse_container<C, V>(
+        container: &C,
+        visitor: &mut V,
+        items: Visits,
+        traverse_items: impl FnOnce(&C, &mut V) -> ControlFlow<V::Break>,
+    ) -> ControlFlow<V::Break>
+    where
+        C: Traversable,
+        V: Visitor,
+    {
+        if V::VISIT_CONTAINERS {
+            visitor.enter_node(container)?;
+        }
+        if items.may_call::<V>() {
+            traverse_items(container, visitor)?;
+        }
+        if V::VISIT_CONTAINERS {
+            visitor.leave_node(container)?;
         }
+        ControlFlow::Continue(())
     }
 
-    // Implement Traversal for container types in standard library.
-    macro_rules! impl_drive_for_into_iterator {
+    pub(crate) fn traverse_container_mut<C, V>(
+        container: &mut C,
+        visitor: &mut V,
+        items: Visits,
+        traverse_items: impl FnOnce(&mut C, &mut V) -> ControlFlow<V::Break>,
+    ) -> ControlFlow<V::Break>
+    where
+        C: TraversableMut,
+        V: VisitorMut,
+    {
+        if V::VISIT_CONTAINERS {
+            visitor.enter_node_mut(container)?;
+        }
+        if items.may_call_mut::<V>() {
+            traverse_items(container, visitor)?;
+        }
+        if V::VISIT_CONTAINERS {
+            visitor.leave_node_mut(container)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    // Implement Traversal for container types in standard library. Containers prefixed with
+    // `indexed` report the index of each element as an edge.
+    macro_rules! impl_traverse_for_into_iterator {
+        ( indexed $type:ty ; $($generics:tt)+ ) => {
+            impl_traverse_for_into_iterator! { @impl true, $type ; $($generics)+ }
+        };
         ( $type:ty ; $($generics:tt)+ ) => {
+            impl_traverse_for_into_iterator! { @impl false, $type ; $($generics)+ }
+        };
+        ( @impl $indexed:literal, $type:ty ; $($generics:tt)+ ) => {
             impl< $($generics)+ > Traversable for $type
             where
                 $type: 'static,
                 for<'a> &'a $type: IntoIterator,
                 for<'a> <&'a $type as IntoIterator>::Item: DerefAndTraverse,
             {
+                const VISITS: Visits = <<&'static $type as IntoIterator>::Item as DerefAndTraverse>::VISITS
+                    .union(Visits::CONTAINERS);
+
                 #[allow(for_loops_over_fallibles)]
                 fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
-                    for item in self {
-                        item.deref_and_traverse(visitor)?;
-                    }
-                    ControlFlow::Continue(())
+                    let items = <<&'static $type as IntoIterator>::Item as DerefAndTraverse>::VISITS;
+                    traverse_container(self, visitor, items, |this, visitor| {
+                        for (index, item) in this.into_iter().enumerate() {
+                            if $indexed {
+                                visitor.enter_edge(Edge::Index(index));
+                            }
+                            item.deref_and_traverse(visitor)?;
+                            if $indexed {
+                                visitor.leave_edge();
+                            }
+                        }
+                        ControlFlow::Continue(())
+                    })
                 }
             }
+        };
+    }
+
+    // Implement both Traversal and mutable Traversal for sequences in standard library.
+    macro_rules! impl_drive_for_into_iterator {
+        ( $type:ty ; $($generics:tt)+ ) => {
+            impl_traverse_for_into_iterator! { indexed $type ; $($generics)+ }
 
             impl< $($generics)+ > TraversableMut for $type
             where
@@ -319,43 +1053,417 @@ mod impl_std_container {
                 for<'a> &'a mut $type: IntoIterator,
                 for<'a> <&'a mut $type as IntoIterator>::Item: DerefAndTraverseMut,
             {
+                const VISITS: Visits =
+                    <<&'static mut $type as IntoIterator>::Item as DerefAndTraverseMut>::VISITS
+                        .union(Visits::CONTAINERS);
+
                 #[allow(for_loops_over_fallibles)]
                 fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
-                    for item in self {
-                        item.deref_and_traverse_mut(visitor)?;
-                    }
-                    ControlFlow::Continue(())
+                    let items =
+                        <<&'static mut $type as IntoIterator>::Item as DerefAndTraverseMut>::VISITS;
+                    traverse_container_mut(self, visitor, items, |this, visitor| {
+                        for (index, item) in this.into_iter().enumerate() {
+                            visitor.enter_edge_mut(Edge::Index(index));
+                            item.deref_and_traverse_mut(visitor)?;
+                            visitor.leave_edge_mut();
+                        }
+                        ControlFlow::Continue(())
+                    })
                 }
             }
         };
     }
 
-    impl_drive_for_into_iterator! { [T] ; T }
     impl_drive_for_into_iterator! { [T; N] ; T, const N: usize }
-    impl_drive_for_into_iterator! { std::vec::Vec<T> ; T }
-    impl_drive_for_into_iterator! { std::collections::BTreeSet<T> ; T }
-    impl_drive_for_into_iterator! { std::collections::BinaryHeap<T> ; T }
-    impl_drive_for_into_iterator! { std::collections::HashSet<T> ; T }
     impl_drive_for_into_iterator! { std::collections::LinkedList<T> ; T }
-    impl_drive_for_into_iterator! { std::collections::VecDeque<T> ; T }
-    impl_drive_for_into_iterator! { std::collections::BTreeMap<T, U> ; T, U }
-    impl_drive_for_into_iterator! { std::collections::HashMap<T, U> ; T, U }
-    impl_drive_for_into_iterator! { Option<T> ; T }
-    impl_drive_for_into_iterator! { Result<T, U> ; T, U }
+
+    impl_traverse_for_into_iterator! { indexed Vec<T> ; T }
+    impl_traverse_for_into_iterator! { indexed VecDeque<T> ; T }
+    impl_traverse_for_into_iterator! { Option<T> ; T }
+
+    impl_traverse_for_into_iterator! { BTreeSet<T> ; T }
+    impl_traverse_for_into_iterator! { BinaryHeap<T> ; T }
+    impl_traverse_for_into_iterator! { HashSet<T, S> ; T, S }
+    impl_traverse_for_into_iterator! { BTreeMap<T, U> ; T, U }
+    impl_traverse_for_into_iterator! { HashMap<T, U, S> ; T, U, S }
+
+    // Slices are unsized, so they cannot be visited as nodes and are always transparent.
+    impl<T: Traversable> Traversable for [T] {
+        const VISITS: Visits = T::VISITS;
+
+        fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+            if !T::VISITS.may_call::<V>() {
+                return ControlFlow::Continue(());
+            }
+            for (index, item) in self.iter().enumerate() {
+                visitor.enter_edge(Edge::Index(index));
+                item.traverse(visitor)?;
+                visitor.leave_edge();
+            }
+            ControlFlow::Continue(())
+        }
+    }
+
+    impl<T: TraversableMut> TraversableMut for [T] {
+        const VISITS: Visits = T::VISITS;
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            if !T::VISITS.may_call_mut::<V>() {
+                return ControlFlow::Continue(());
+            }
+            for (index, item) in self.iter_mut().enumerate() {
+                visitor.enter_edge_mut(Edge::Index(index));
+                item.traverse_mut(visitor)?;
+                visitor.leave_edge_mut();
+            }
+            ControlFlow::Continue(())
+        }
+    }
+
+    // Take the value out of an edit, unless it does not have the type of the elements of the
+    // container, in which case the edit is ignored.
+    fn edit_value<T: Any>(value: Box<dyn Any>) -> Option<T> {
+        value.downcast::<T>().ok().map(|value| *value)
+    }
+
+    // Ask the visitor for an edit at an element that has been traversed, if it applies edits.
+    fn edit_element<V: VisitorMut>(visitor: &mut V, item: &mut dyn Any) -> Edit {
+        if V::EDITS {
+            visitor.edit_mut(item)
+        } else {
+            Edit::Keep
+        }
+    }
+
+    // Implement mutable Traversal with structural edits for sequences in standard library.
+    macro_rules! impl_traverse_mut_for_sequence {
+        ( $type:ty ; $($generics:tt)+ ) => {
+            impl< $($generics)+ > TraversableMut for $type
+            where
+                T: TraversableMut,
+            {
+                const VISITS: Visits = T::VISITS.union(Visits::CONTAINERS);
+
+                fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+                    traverse_container_mut(self, visitor, T::VISITS, |this, visitor| {
+                        let mut index = 0;
+                        while index < this.len() {
+                            visitor.enter_edge_mut(Edge::Index(index));
+                            this[index].traverse_mut(visitor)?;
+                            visitor.leave_edge_mut();
+                            match edit_element(visitor, &mut this[index]) {
+                                Edit::Keep => index += 1,
+                                Edit::Remove => {
+                                    this.remove(index);
+                                }
+                                Edit::Replace(value) => {
+                                    if let Some(value) = edit_value(value) {
+                                        this[index] = value;
+                                    }
+                                    index += 1;
+                                }
+                                Edit::InsertBefore(value) => match edit_value(value) {
+                                    Some(value) => {
+                                        this.insert(index, value);
+                                        index += 2;
+                                    }
+                                    None => index += 1,
+                                },
+                                Edit::InsertAfter(value) => match edit_value(value) {
+                                    Some(value) => {
+                                        this.insert(index + 1, value);
+                                        index += 2;
+                                    }
+                                    None => index += 1,
+                                },
+                            }
+                        }
+                        ControlFlow::Continue(())
+                    })
+                }
+            }
+        };
+    }
+
+    impl_traverse_mut_for_sequence! { Vec<T> ; T }
+    impl_traverse_mut_for_sequence! { VecDeque<T> ; T }
+
+    impl<T: TraversableMut> TraversableMut for Option<T> {
+        const VISITS: Visits = T::VISITS.union(Visits::CONTAINERS);
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            traverse_container_mut(self, visitor, T::VISITS, |this, visitor| {
+                if let Some(item) = this {
+                    item.traverse_mut(visitor)?;
+                    match edit_element(visitor, item) {
+                        Edit::Keep => {}
+                        Edit::Remove => *this = None,
+                        Edit::Replace(value) => {
+                            if let Some(value) = edit_value(value) {
+                                *item = value;
+                            }
+                        }
+                        Edit::InsertBefore(_) | Edit::InsertAfter(_) => {}
+                    }
+                }
+                ControlFlow::Continue(())
+            })
+        }
+    }
+
+    // Sets do not hand out mutable references to their elements, since mutating them in place could
+    // break the ordering or hashing invariants of the collection. Instead, the elements are taken
+    // out, traversed, and inserted back. The same applies to the keys of maps, see [`keys_mut`].
+    //
+    // Elements are inserted back even if the traversal breaks early or panics, so no element is
+    // lost, unless `traverse` removes them by returning `false`. If the visitor makes two elements
+    // (or keys) equal, only one of them is kept.
+    pub(crate) fn traverse_mut_and_reinsert<T, V>(
+        items: impl IntoIterator<Item = T>,
+        visitor: &mut V,
+        mut traverse: impl FnMut(&mut T, &mut V) -> ControlFlow<V::Break, bool>,
+        reinsert: impl FnMut(T),
+    ) -> ControlFlow<V::Break>
+    where
+        V: VisitorMut,
+    {
+        let mut guard = Reinsert {
+            current: None,
+            items: items.into_iter(),
+            reinsert,
+        };
+        for item in guard.items.by_ref() {
+            let item = guard.current.insert(item);
+            match traverse(item, visitor) {
+                ControlFlow::Continue(true) => {}
+                ControlFlow::Continue(false) => guard.current = None,
+                ControlFlow::Break(value) => return ControlFlow::Break(value),
+            }
+            if let Some(item) = guard.current.take() {
+                (guard.reinsert)(item);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    // Inserts back the element being traversed and the remaining ones when dropped.
+    struct Reinsert<I: Iterator, F: FnMut(I::Item)> {
+        current: Option<I::Item>,
+        items: I,
+        reinsert: F,
+    }
+
+    impl<I: Iterator, F: FnMut(I::Item)> Drop for Reinsert<I, F> {
+        fn drop(&mut self) {
+            if let Some(item) = self.current.take() {
+                (self.reinsert)(item);
+            }
+            for item in &mut self.items {
+                (self.reinsert)(item);
+            }
+        }
+    }
+
+    pub(crate) fn traverse_element_mut<T, V>(
+        item: &mut T,
+        visitor: &mut V,
+    ) -> ControlFlow<V::Break, bool>
+    where
+        T: TraversableMut,
+        V: VisitorMut,
+    {
+        item.traverse_mut(visitor).map_continue(|()| true)
+    }
+
+    // Traverse a map value and apply the edit on it, returning whether to keep its entry.
+    pub(crate) fn traverse_value_mut<U, V>(
+        value: &mut U,
+        visitor: &mut V,
+    ) -> ControlFlow<V::Break, bool>
+    where
+        U: TraversableMut,
+        V: VisitorMut,
+    {
+        value.traverse_mut(visitor)?;
+        match edit_element(visitor, value) {
+            Edit::Keep => ControlFlow::Continue(true),
+            Edit::Remove => ControlFlow::Continue(false),
+            Edit::Replace(replacement) => {
+                if let Some(replacement) = edit_value(replacement) {
+                    *value = replacement;
+                }
+                ControlFlow::Continue(true)
+            }
+            Edit::InsertBefore(_) | Edit::InsertAfter(_) => ControlFlow::Continue(true),
+        }
+    }
+
+    // Traverse a map entry and apply the edit on its value, returning whether to keep the entry.
+    pub(crate) fn traverse_entry_mut<T, U, V>(
+        (key, value): &mut (T, U),
+        visitor: &mut V,
+    ) -> ControlFlow<V::Break, bool>
+    where
+        T: TraversableMut,
+        U: TraversableMut,
+        V: VisitorMut,
+    {
+        key.traverse_mut(visitor)?;
+        traverse_value_mut(value, visitor)
+    }
+
+    // Traverse a map value for `retain`, returning whether to keep its entry. Once traversal
+    // breaks, the remaining entries are kept without being traversed.
+    pub(crate) fn retain_value_mut<U, V>(
+        value: &mut U,
+        visitor: &mut V,
+        result: &mut ControlFlow<V::Break>,
+    ) -> bool
+    where
+        U: TraversableMut,
+        V: VisitorMut,
+    {
+        if result.is_break() {
+            return true;
+        }
+        match traverse_value_mut(value, visitor) {
+            ControlFlow::Continue(keep) => keep,
+            ControlFlow::Break(value) => {
+                *result = ControlFlow::Break(value);
+                true
+            }
+        }
+    }
+
+    impl<T> TraversableMut for BTreeSet<T>
+    where
+        T: TraversableMut + Ord,
+    {
+        const VISITS: Visits = T::VISITS.union(Visits::CONTAINERS);
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            traverse_container_mut(self, visitor, T::VISITS, |this, visitor| {
+                let items = core::mem::take(this);
+                traverse_mut_and_reinsert(items, visitor, traverse_element_mut, |item| {
+                    this.insert(item);
+                })
+            })
+        }
+    }
+
+    impl<T> TraversableMut for BinaryHeap<T>
+    where
+        T: TraversableMut + Ord,
+    {
+        const VISITS: Visits = T::VISITS.union(Visits::CONTAINERS);
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            traverse_container_mut(self, visitor, T::VISITS, |this, visitor| {
+                let items = core::mem::take(this).into_vec();
+                traverse_mut_and_reinsert(items, visitor, traverse_element_mut, |item| {
+                    this.push(item)
+                })
+            })
+        }
+    }
+
+    impl<T, S> TraversableMut for HashSet<T, S>
+    where
+        T: TraversableMut + Eq + Hash,
+        S: BuildHasher + 'static,
+    {
+        const VISITS: Visits = T::VISITS.union(Visits::CONTAINERS);
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            traverse_container_mut(self, visitor, T::VISITS, |this, visitor| {
+                let items = this.drain().collect::<Vec<_>>();
+                traverse_mut_and_reinsert(items, visitor, traverse_element_mut, |item| {
+                    this.insert(item);
+                })
+            })
+        }
+    }
+
+    // Maps only hand out mutable references to their values, so their keys are not traversed
+    // mutably, unless with the functions of [`keys_mut`].
+    impl<T, U> TraversableMut for BTreeMap<T, U>
+    where
+        T: Ord + 'static,
+        U: TraversableMut,
+    {
+        const VISITS: Visits = U::VISITS.union(Visits::CONTAINERS);
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            traverse_container_mut(self, visitor, U::VISITS, |this, visitor| {
+                let mut result = ControlFlow::Continue(());
+                this.retain(|_, value| retain_value_mut(value, visitor, &mut result));
+                result
+            })
+        }
+    }
+
+    impl<T, U, S> TraversableMut for HashMap<T, U, S>
+    where
+        T: 'static,
+        U: TraversableMut,
+        S: 'static,
+    {
+        const VISITS: Visits = U::VISITS.union(Visits::CONTAINERS);
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            traverse_container_mut(self, visitor, U::VISITS, |this, visitor| {
+                let mut result = ControlFlow::Continue(());
+                this.retain(|_, value| retain_value_mut(value, visitor, &mut result));
+                result
+            })
+        }
+    }
+
+    // `Result` only iterates over its `Ok` value, so it is implemented separately to traverse
+    // whichever variant is present.
+    impl<T: Traversable, U: Traversable> Traversable for Result<T, U> {
+        const VISITS: Visits = T::VISITS.union(U::VISITS).union(Visits::CONTAINERS);
+
+        fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+            let items = T::VISITS.union(U::VISITS);
+            traverse_container(self, visitor, items, |this, visitor| match this {
+                Ok(value) => value.traverse(visitor),
+                Err(error) => error.traverse(visitor),
+            })
+        }
+    }
+
+    impl<T: TraversableMut, U: TraversableMut> TraversableMut for Result<T, U> {
+        const VISITS: Visits = T::VISITS.union(U::VISITS).union(Visits::CONTAINERS);
+
+        fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
+            let items = T::VISITS.union(U::VISITS);
+            traverse_container_mut(self, visitor, items, |this, visitor| match this {
+                Ok(value) => value.traverse_mut(visitor),
+                Err(error) => error.traverse_mut(visitor),
+            })
+        }
+    }
 
     impl<T: Traversable> Traversable for Box<T> {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
             (**self).traverse(visitor)
         }
     }
 
     impl<T: TraversableMut> TraversableMut for Box<T> {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
             (**self).traverse_mut(visitor)
         }
     }
 
     impl<T: Traversable> Traversable for Arc<T> {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
             (**self).traverse(visitor)
         }
@@ -365,6 +1473,8 @@ mod impl_std_container {
     where
         T: Traversable,
     {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
             let lock = self.lock().unwrap();
             lock.traverse(visitor)
@@ -375,6 +1485,8 @@ mod impl_std_container {
     where
         T: Traversable,
     {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
             let lock = self.read().unwrap();
             lock.traverse(visitor)
@@ -385,6 +1497,8 @@ mod impl_std_container {
     where
         T: TraversableMut,
     {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
             let mut lock = self.lock().unwrap();
             lock.traverse_mut(visitor)
@@ -395,6 +1509,8 @@ mod impl_std_container {
     where
         T: TraversableMut,
     {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
             let mut lock = self.write().unwrap();
             lock.traverse_mut(visitor)
@@ -405,6 +1521,8 @@ mod impl_std_container {
     where
         T: Traversable + Copy,
     {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
             self.get().traverse(visitor)
         }
@@ -414,6 +1532,8 @@ mod impl_std_container {
     where
         T: TraversableMut,
     {
+        const VISITS: Visits = T::VISITS;
+
         fn traverse_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break> {
             self.get_mut().traverse_mut(visitor)
         }
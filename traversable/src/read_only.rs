@@ -0,0 +1,134 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mutable traversal with visitors that only read.
+
+use core::any::Any;
+use core::ops::ControlFlow;
+
+use crate::Edge;
+use crate::Finish;
+use crate::Visitor;
+use crate::VisitorMut;
+
+/// Adapts a [`Visitor`] to a [`VisitorMut`], so that a visitor that only reads the nodes needs a
+/// single definition to be used in both immutable and mutable traversals.
+///
+/// The traversal policy, [`Visitor::enter`], [`Visitor::leave`], the edge and typed leaf hooks are
+/// forwarded to the wrapped visitor, with the nodes reborrowed immutably. Since mutable nodes are
+/// not necessarily [`Traversable`](crate::Traversable), [`Visitor::enter_node`] and
+/// [`Visitor::leave_node`] cannot be called, so visitors that set [`Visitor::NODE_HOOKS`] are
+/// rejected at compile time:
+///
+/// ```compile_fail
+/// use core::fmt::Debug;
+/// use core::ops::ControlFlow;
+///
+/// use traversable::cast::make_cast_visitor;
+/// use traversable::read_only::ReadOnly;
+///
+/// let visitor = make_cast_visitor::<dyn Debug, (), _>(|_| ControlFlow::Continue(()));
+/// let _ = ReadOnly::new(visitor);
+/// ```
+pub struct ReadOnly<V> {
+    visitor: V,
+}
+
+impl<V: Visitor> ReadOnly<V> {
+    /// Create an adapter for `visitor`.
+    pub fn new(visitor: V) -> Self {
+        const {
+            assert!(
+                !V::NODE_HOOKS,
+                "the node hooks of the visitor cannot be called in mutable traversals"
+            );
+        }
+        Self { visitor }
+    }
+}
+
+impl<V> ReadOnly<V> {
+    /// Returns the wrapped visitor.
+    pub fn into_inner(self) -> V {
+        self.visitor
+    }
+}
+
+impl<V: Finish> Finish for ReadOnly<V> {
+    type Output = V::Output;
+
+    fn finish(self) -> Self::Output {
+        self.visitor.finish()
+    }
+}
+
+// Forward typed leaf hooks of `VisitorMut` to the hooks of `Visitor`.
+macro_rules! forward_visit_mut {
+    ( $( $name:ident($type:ty) => $visit:ident; )* ) => {
+        $(
+            fn $name(&mut self, value: &mut $type) -> ControlFlow<Self::Break> {
+                self.visitor.$visit(*value as _)
+            }
+        )*
+    };
+}
+
+impl<V: Visitor> VisitorMut for ReadOnly<V> {
+    type Break = V::Break;
+
+    const DETERMINISTIC_ORDER: bool = V::DETERMINISTIC_ORDER;
+    const VISIT_CONTAINERS: bool = V::VISIT_CONTAINERS;
+    const VISIT_TRIVIAL: bool = V::VISIT_TRIVIAL;
+    const VISIT_STD: bool = V::VISIT_STD;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        self.visitor.enter(this)
+    }
+
+    fn leave_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        self.visitor.leave(this)
+    }
+
+    fn enter_edge_mut(&mut self, edge: Edge) {
+        self.visitor.enter_edge(edge);
+    }
+
+    fn leave_edge_mut(&mut self) {
+        self.visitor.leave_edge();
+    }
+
+    forward_visit_mut! {
+        visit_bool_mut(bool) => visit_bool;
+        visit_i8_mut(i8) => visit_i8;
+        visit_i16_mut(i16) => visit_i16;
+        visit_i32_mut(i32) => visit_i32;
+        visit_i64_mut(i64) => visit_i64;
+        visit_i128_mut(i128) => visit_i128;
+        visit_isize_mut(isize) => visit_i64;
+        visit_u8_mut(u8) => visit_u8;
+        visit_u16_mut(u16) => visit_u16;
+        visit_u32_mut(u32) => visit_u32;
+        visit_u64_mut(u64) => visit_u64;
+        visit_u128_mut(u128) => visit_u128;
+        visit_usize_mut(usize) => visit_u64;
+        visit_f32_mut(f32) => visit_f32;
+        visit_f64_mut(f64) => visit_f64;
+        visit_char_mut(char) => visit_char;
+    }
+
+    #[cfg(feature = "std")]
+    fn visit_string_mut(&mut self, value: &mut std::string::String) -> ControlFlow<Self::Break> {
+        self.visitor.visit_str(value)
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Static metadata about the fields and variants of traversable types.
+//!
+//! Derived types describe themselves with a [`TypeSchema`] through [`Traversable::SCHEMA`], so
+//! that tools like printers or exporters can be written once for all of them, e.g., in
+//! [`Visitor::enter_node`].
+//!
+//! [`Traversable::SCHEMA`]: crate::Traversable::SCHEMA
+//! [`Visitor::enter_node`]: crate::Visitor::enter_node
+
+use core::any::TypeId;
+
+use crate::Edge;
+
+/// The layout of a struct or an enum.
+#[derive(Debug, Clone, Copy)]
+pub struct TypeSchema {
+    /// The name of the type, without its generic arguments.
+    pub name: &'static str,
+    /// Whether the type is a struct or an enum, with its fields or variants.
+    pub kind: SchemaKind,
+}
+
+/// The kind of a [`TypeSchema`].
+#[derive(Debug, Clone, Copy)]
+pub enum SchemaKind {
+    /// A struct with its fields, in declaration order.
+    Struct(&'static [FieldSchema]),
+    /// An enum with its variants, in declaration order.
+    Enum(&'static [VariantSchema]),
+}
+
+/// A variant of an enum.
+#[derive(Debug, Clone, Copy)]
+pub struct VariantSchema {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The fields of the variant, in declaration order.
+    pub fields: &'static [FieldSchema],
+    /// Whether the variant is never traversed.
+    pub skipped: bool,
+}
+
+/// A field of a struct or an enum variant.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSchema {
+    /// The name or the index of the field, as reported by [`Visitor::enter_edge`].
+    ///
+    /// [`Visitor::enter_edge`]: crate::Visitor::enter_edge
+    pub edge: Edge,
+    /// Returns the name of the type of the field, see [`core::any::type_name`].
+    pub type_name: fn() -> &'static str,
+    /// Returns the `TypeId` of the type of the field.
+    pub type_id: fn() -> TypeId,
+    /// Whether the field is never traversed by immutable traversal.
+    ///
+    /// Fields skipped conditionally at traversal time are not marked.
+    pub skipped: bool,
+}
+
+impl TypeSchema {
+    /// Returns the fields of a struct, or an empty slice for an enum.
+    pub fn fields(&self) -> &'static [FieldSchema] {
+        match self.kind {
+            SchemaKind::Struct(fields) => fields,
+            SchemaKind::Enum(_) => &[],
+        }
+    }
+
+    /// Returns the variants of an enum, or an empty slice for a struct.
+    pub fn variants(&self) -> &'static [VariantSchema] {
+        match self.kind {
+            SchemaKind::Struct(_) => &[],
+            SchemaKind::Enum(variants) => variants,
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic traversal of hash-based collections.
+//!
+//! Hash-based collections are traversed in their iteration order, which differs between runs. The
+//! functions of this module traverse them in ascending order of their elements (keys for maps) if
+//! the visitor sets [`Visitor::DETERMINISTIC_ORDER`], which requires the elements to be `Ord`.
+//! Otherwise, they behave like the implementations of [`Traversable`] and [`TraversableMut`].
+//!
+//! They can be used for fields of derived types with the `with_ref` and `with_mut` attributes,
+//! e.g., `#[traverse(with_ref = "traversable::sorted::hash_map", with_mut =
+//! "traversable::sorted::hash_map_mut")]`.
+
+use core::hash::BuildHasher;
+use core::hash::Hash;
+use core::ops::ControlFlow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::vec::Vec;
+
+use crate::Traversable;
+use crate::TraversableMut;
+use crate::Visitor;
+use crate::VisitorMut;
+use crate::impl_std_container::retain_value_mut;
+use crate::impl_std_container::traverse_container;
+use crate::impl_std_container::traverse_container_mut;
+use crate::impl_std_container::traverse_element_mut;
+use crate::impl_std_container::traverse_entry_mut;
+use crate::impl_std_container::traverse_mut_and_reinsert;
+
+// Sort the items of a hash-based collection by key for `DETERMINISTIC_ORDER` traversals.
+fn sort_by_key<T, K: Ord + ?Sized>(items: &mut [T], key: impl Fn(&T) -> &K) {
+    items.sort_unstable_by(|a, b| key(a).cmp(key(b)));
+}
+
+/// Traverse the elements of a `HashSet`.
+pub fn hash_set<T, S, V>(set: &HashSet<T, S>, visitor: &mut V) -> ControlFlow<V::Break>
+where
+    T: Traversable + Ord,
+    S: 'static,
+    V: Visitor,
+{
+    traverse_container(set, visitor, T::VISITS, |this, visitor| {
+        let mut items = this.iter().collect::<Vec<_>>();
+        if V::DETERMINISTIC_ORDER {
+            sort_by_key(&mut items, |item| *item);
+        }
+        for item in items {
+            item.traverse(visitor)?;
+        }
+        ControlFlow::Continue(())
+    })
+}
+
+/// Traverse the elements of a `HashSet` mutably.
+pub fn hash_set_mut<T, S, V>(set: &mut HashSet<T, S>, visitor: &mut V) -> ControlFlow<V::Break>
+where
+    T: TraversableMut + Ord + Hash,
+    S: BuildHasher + 'static,
+    V: VisitorMut,
+{
+    traverse_container_mut(set, visitor, T::VISITS, |this, visitor| {
+        let mut items = this.drain().collect::<Vec<_>>();
+        if V::DETERMINISTIC_ORDER {
+            sort_by_key(&mut items, |item| item);
+        }
+        traverse_mut_and_reinsert(items, visitor, traverse_element_mut, |item| {
+            this.insert(item);
+        })
+    })
+}
+
+/// Traverse the keys and values of a `HashMap`.
+pub fn hash_map<K, U, S, V>(map: &HashMap<K, U, S>, visitor: &mut V) -> ControlFlow<V::Break>
+where
+    K: Traversable + Ord,
+    U: Traversable,
+    S: 'static,
+    V: Visitor,
+{
+    traverse_container(map, visitor, K::VISITS.union(U::VISITS), |this, visitor| {
+        let mut entries = this.iter().collect::<Vec<_>>();
+        if V::DETERMINISTIC_ORDER {
+            sort_by_key(&mut entries, |(key, _)| *key);
+        }
+        for (key, value) in entries {
+            key.traverse(visitor)?;
+            value.traverse(visitor)?;
+        }
+        ControlFlow::Continue(())
+    })
+}
+
+/// Traverse the values of a `HashMap` mutably.
+pub fn hash_map_mut<K, U, S, V>(
+    map: &mut HashMap<K, U, S>,
+    visitor: &mut V,
+) -> ControlFlow<V::Break>
+where
+    K: Ord + 'static,
+    U: TraversableMut,
+    S: 'static,
+    V: VisitorMut,
+{
+    traverse_container_mut(map, visitor, U::VISITS, |this, visitor| {
+        let mut result = ControlFlow::Continue(());
+        if !V::DETERMINISTIC_ORDER {
+            this.retain(|_, value| retain_value_mut(value, visitor, &mut result));
+            return result;
+        }
+        let mut entries = this.iter_mut().collect::<Vec<_>>();
+        sort_by_key(&mut entries, |(key, _)| *key);
+        // The entries to remove are identified by the address of their key, which does not change
+        // until the map is modified by `retain`.
+        let mut removed = Vec::new();
+        for (key, value) in entries {
+            if !retain_value_mut(value, visitor, &mut result) {
+                removed.push(key as *const K);
+            }
+        }
+        if !removed.is_empty() {
+            removed.sort_unstable();
+            this.retain(|key, _| removed.binary_search(&(key as *const K)).is_err());
+        }
+        result
+    })
+}
+
+/// Traverse the keys and values of a `HashMap` mutably, see [`keys_mut`](crate::keys_mut).
+pub fn hash_map_keys_mut<K, U, S, V>(
+    map: &mut HashMap<K, U, S>,
+    visitor: &mut V,
+) -> ControlFlow<V::Break>
+where
+    K: TraversableMut + Ord + Hash,
+    U: TraversableMut,
+    S: BuildHasher + 'static,
+    V: VisitorMut,
+{
+    traverse_container_mut(map, visitor, K::VISITS.union(U::VISITS), |this, visitor| {
+        let mut entries = this.drain().collect::<Vec<_>>();
+        if V::DETERMINISTIC_ORDER {
+            sort_by_key(&mut entries, |(key, _)| key);
+        }
+        traverse_mut_and_reinsert(entries, visitor, traverse_entry_mut, |(key, value)| {
+            this.insert(key, value);
+        })
+    })
+}
@@ -0,0 +1,61 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Helpers shared by the tests of the derive, which include this module.
+#![allow(dead_code)]
+
+use std::any::Any;
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+
+#[derive(Debug, Clone, PartialEq, Traversable, TraversableMut)]
+pub struct Leaf(pub u32);
+
+// Collects the values of the leaves in the order they are entered.
+#[derive(Default)]
+pub struct CollectLeaves(pub Vec<u32>);
+
+impl Visitor for CollectLeaves {
+    type Break = ();
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(leaf) = this.downcast_ref::<Leaf>() {
+            self.0.push(leaf.0);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl VisitorMut for CollectLeaves {
+    type Break = ();
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        self.enter(this)
+    }
+}
+
+// Collects the leaves of `data`, checking that immutable and mutable traversals agree, which
+// they do unless `data` contains maps with keys that contain leaves, see `TraversableMut`.
+pub fn collect<T: Traversable + TraversableMut>(mut data: T) -> Vec<u32> {
+    let mut visitor = CollectLeaves::default();
+    assert!(data.traverse(&mut visitor).is_continue());
+    let leaves = std::mem::take(&mut visitor.0);
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(leaves, visitor.0);
+    leaves
+}
@@ -0,0 +1,87 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+mod common;
+
+use std::marker::PhantomData;
+
+use common::Leaf;
+use common::collect;
+use traversable::Traversable;
+use traversable::TraversableMut;
+
+#[derive(Traversable, TraversableMut)]
+struct Node<T> {
+    children: Vec<T>,
+}
+
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+enum Tagged<T, M> {
+    Value(T, #[traverse(skip)] PhantomData<M>),
+    #[traverse(skip)]
+    Marker(M),
+}
+
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+struct Holder<I: Iterator> {
+    #[traverse(bound(
+        traverse = "I::Item: Traversable",
+        traverse_mut = "I::Item: TraversableMut"
+    ))]
+    item: I::Item,
+    #[traverse(skip)]
+    iter: I,
+}
+
+#[derive(Traversable, TraversableMut)]
+#[traverse(bound = "T: Traversable + TraversableMut + Clone")]
+struct Pair<T: Clone> {
+    first: T,
+    second: T,
+}
+
+#[test]
+fn test_inferred_bounds() {
+    let node = Node {
+        children: vec![Leaf(1), Leaf(2)],
+    };
+    assert_eq!(collect(node), vec![1, 2]);
+
+    let tagged = Tagged::<Node<Leaf>, String>::Value(
+        Node {
+            children: vec![Leaf(1)],
+        },
+        PhantomData,
+    );
+    assert_eq!(collect(tagged), vec![1]);
+}
+
+#[test]
+fn test_custom_bounds() {
+    let holder = Holder {
+        item: Leaf(1),
+        iter: vec![Leaf(2)].into_iter(),
+    };
+    assert_eq!(collect(holder), vec![1]);
+
+    let pair = Pair {
+        first: Some(Leaf(1)),
+        second: None,
+    };
+    assert_eq!(collect(pair), vec![1]);
+}
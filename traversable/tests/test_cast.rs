@@ -0,0 +1,116 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::cast::CastRegistry;
+use traversable::cast::make_cast_visitor;
+
+trait Spanned {
+    fn span(&self) -> (usize, usize);
+}
+
+#[derive(Debug, Traversable)]
+#[traverse(as_dyn(Spanned, Debug))]
+struct Ident {
+    #[traverse(skip)]
+    start: usize,
+    #[traverse(skip)]
+    name: &'static str,
+}
+
+impl Spanned for Ident {
+    fn span(&self) -> (usize, usize) {
+        (self.start, self.start + self.name.len())
+    }
+}
+
+#[derive(Debug, Traversable)]
+#[traverse(as_dyn(Spanned))]
+struct Call {
+    #[traverse(skip)]
+    start: usize,
+    func: Ident,
+    args: Vec<Arg>,
+}
+
+impl Spanned for Call {
+    fn span(&self) -> (usize, usize) {
+        (self.start, self.start + 4)
+    }
+}
+
+#[derive(Debug, Traversable)]
+struct Arg(Ident);
+
+fn call() -> Call {
+    Call {
+        start: 0,
+        func: Ident {
+            start: 0,
+            name: "f",
+        },
+        args: vec![Arg(Ident {
+            start: 2,
+            name: "x",
+        })],
+    }
+}
+
+#[test]
+fn test_cast_visitor() {
+    let mut spans = Vec::new();
+    let visitor = make_cast_visitor::<dyn Spanned, (), _>(|node| {
+        spans.push(node.span());
+        ControlFlow::Continue(())
+    });
+    assert_eq!(call().visit_with(visitor), Ok(()));
+    assert_eq!(spans, [(0, 4), (0, 1), (2, 3)]);
+
+    let mut count = 0;
+    let mut visitor = make_cast_visitor::<dyn Debug, (), _>(|_| {
+        count += 1;
+        ControlFlow::Continue(())
+    });
+    assert!(call().traverse(&mut visitor).is_continue());
+    let registry = visitor.into_registry();
+    assert_eq!(count, 2);
+
+    let arg = Arg(Ident {
+        start: 0,
+        name: "y",
+    });
+    assert!(registry.cast::<dyn Debug>(&arg.0).is_some());
+    assert!(registry.cast::<dyn Debug>(&arg as &dyn Any).is_none());
+}
+
+#[test]
+fn test_cast_registry() {
+    let mut registry = CastRegistry::new();
+    registry.register_type::<Call>();
+
+    let call = call();
+    let node: &dyn Any = &call;
+    assert_eq!(
+        registry.cast::<dyn Spanned>(node).map(|s| s.span()),
+        Some((0, 4))
+    );
+    assert!(registry.cast::<dyn Debug>(node).is_none());
+    assert!(registry.cast::<dyn Spanned>(&call.func).is_none());
+}
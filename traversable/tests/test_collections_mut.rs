@@ -0,0 +1,194 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::BuildHasherDefault;
+use std::hash::DefaultHasher;
+use std::ops::ControlFlow;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+use traversable::keys_mut;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Traversable, TraversableMut)]
+struct Ident(#[traverse(skip)] String);
+
+fn ident(name: &str) -> Ident {
+    Ident(name.to_string())
+}
+
+struct Lowercase;
+
+impl VisitorMut for Lowercase {
+    type Break = ();
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(Ident(name)) = this.downcast_mut::<Ident>() {
+            *name = name.to_lowercase();
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+struct Count(usize);
+
+impl Visitor for Count {
+    type Break = ();
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if this.is::<Ident>() {
+            self.0 += 1;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+struct StopAt(&'static str);
+
+impl VisitorMut for StopAt {
+    type Break = ();
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        match this.downcast_mut::<Ident>() {
+            Some(Ident(name)) if name == self.0 => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+#[test]
+fn test_sets() {
+    let mut set = BTreeSet::from([ident("B"), ident("a")]);
+    assert!(set.traverse_mut(&mut Lowercase).is_continue());
+    assert_eq!(set, BTreeSet::from([ident("a"), ident("b")]));
+
+    let mut set = HashSet::from([ident("B"), ident("a")]);
+    assert!(set.traverse_mut(&mut Lowercase).is_continue());
+    assert_eq!(set, HashSet::from([ident("a"), ident("b")]));
+
+    let mut heap = BinaryHeap::from([ident("B"), ident("a")]);
+    assert!(heap.traverse_mut(&mut Lowercase).is_continue());
+    assert_eq!(heap.into_sorted_vec(), vec![ident("a"), ident("b")]);
+}
+
+#[test]
+fn test_map_values() {
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Key(u32);
+
+    let mut map = BTreeMap::from([(Key(0), ident("Y"))]);
+    assert!(map.traverse_mut(&mut Lowercase).is_continue());
+    assert_eq!(map, BTreeMap::from([(Key(0), ident("y"))]));
+
+    let mut map = HashMap::from([(ident("X"), ident("Y"))]);
+    assert!(map.traverse_mut(&mut Lowercase).is_continue());
+    assert_eq!(map, HashMap::from([(ident("X"), ident("y"))]));
+}
+
+#[test]
+fn test_map_keys() {
+    let mut map = BTreeMap::from([(ident("X"), ident("Y"))]);
+    assert!(keys_mut::btree_map(&mut map, &mut Lowercase).is_continue());
+    assert_eq!(map, BTreeMap::from([(ident("x"), ident("y"))]));
+
+    let mut map = HashMap::from([(ident("X"), ident("Y"))]);
+    assert!(keys_mut::hash_map(&mut map, &mut Lowercase).is_continue());
+    assert_eq!(map, HashMap::from([(ident("x"), ident("y"))]));
+
+    #[derive(TraversableMut)]
+    struct Scope {
+        #[traverse(with_mut = "keys_mut::btree_map")]
+        names: BTreeMap<Ident, Ident>,
+    }
+
+    let mut scope = Scope {
+        names: BTreeMap::from([(ident("A"), ident("B"))]),
+    };
+    assert!(scope.traverse_mut(&mut Lowercase).is_continue());
+    assert_eq!(scope.names, BTreeMap::from([(ident("a"), ident("b"))]));
+}
+
+#[test]
+fn test_break_keeps_elements() {
+    let mut set = BTreeSet::from([ident("a"), ident("b"), ident("c")]);
+    assert!(set.traverse_mut(&mut StopAt("b")).is_break());
+    assert_eq!(set.len(), 3);
+
+    let mut map = HashMap::from([(ident("a"), ident("b")), (ident("c"), ident("d"))]);
+    assert!(map.traverse_mut(&mut StopAt("b")).is_break());
+    assert_eq!(map.len(), 2);
+
+    let mut map = BTreeMap::from([(ident("a"), ident("b")), (ident("c"), ident("d"))]);
+    assert!(keys_mut::btree_map(&mut map, &mut StopAt("c")).is_break());
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_panic_keeps_elements() {
+    struct PanicAt(&'static str);
+
+    impl VisitorMut for PanicAt {
+        type Break = ();
+
+        fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+            match this.downcast_mut::<Ident>() {
+                Some(Ident(name)) if name == self.0 => panic!("visiting {name}"),
+                _ => ControlFlow::Continue(()),
+            }
+        }
+    }
+
+    let mut set = BTreeSet::from([ident("a"), ident("b"), ident("c")]);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| set.traverse_mut(&mut PanicAt("b"))));
+    assert!(result.is_err());
+    assert_eq!(set.len(), 3);
+
+    let mut map = HashMap::from([(ident("a"), ident("b")), (ident("c"), ident("d"))]);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        keys_mut::hash_map(&mut map, &mut PanicAt("a"))
+    }));
+    assert!(result.is_err());
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_custom_hasher() {
+    type Hasher = BuildHasherDefault<DefaultHasher>;
+
+    let mut set = HashSet::<_, Hasher>::from_iter([ident("B"), ident("a")]);
+    assert!(set.traverse_mut(&mut Lowercase).is_continue());
+    assert!(set.contains(&ident("b")));
+
+    let mut map = HashMap::<_, _, Hasher>::from_iter([(ident("X"), ident("Y"))]);
+    assert!(map.traverse_mut(&mut Lowercase).is_continue());
+    assert_eq!(map.get(&ident("X")), Some(&ident("y")));
+    assert!(keys_mut::hash_map(&mut map, &mut Lowercase).is_continue());
+    assert_eq!(map.get(&ident("x")), Some(&ident("y")));
+
+    let mut count = Count(0);
+    assert!(set.traverse(&mut count).is_continue());
+    assert!(map.traverse(&mut count).is_continue());
+    assert_eq!(count.0, 4);
+}
@@ -0,0 +1,120 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+
+#[derive(Traversable, TraversableMut)]
+struct Block {
+    stmts: Vec<Stmt>,
+}
+
+#[derive(Traversable, TraversableMut)]
+struct Stmt {
+    expr: Option<Expr>,
+}
+
+#[derive(Traversable, TraversableMut)]
+struct Expr;
+
+fn block() -> Block {
+    Block {
+        stmts: vec![Stmt { expr: Some(Expr) }, Stmt { expr: None }],
+    }
+}
+
+#[derive(Default)]
+struct Events(Vec<String>);
+
+impl Visitor for Events {
+    type Break = ();
+
+    const VISIT_CONTAINERS: bool = true;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(stmts) = this.downcast_ref::<Vec<Stmt>>() {
+            self.0.push(format!("enter stmts({})", stmts.len()));
+        } else if let Some(expr) = this.downcast_ref::<Option<Expr>>() {
+            self.0.push(format!("enter expr({})", expr.is_some()));
+        } else if this.is::<Expr>() {
+            self.0.push("expr".to_string());
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn leave(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if this.is::<Vec<Stmt>>() {
+            self.0.push("leave stmts".to_string());
+        } else if this.is::<Option<Expr>>() {
+            self.0.push("leave expr".to_string());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_visit_containers() {
+    let mut events = Events::default();
+    assert!(block().traverse(&mut events).is_continue());
+    assert_eq!(
+        events.0,
+        [
+            "enter stmts(2)",
+            "enter expr(true)",
+            "expr",
+            "leave expr",
+            "enter expr(false)",
+            "leave expr",
+            "leave stmts",
+        ]
+    );
+}
+
+struct FillEmpty {
+    exprs: usize,
+}
+
+impl VisitorMut for FillEmpty {
+    type Break = ();
+
+    const VISIT_CONTAINERS: bool = true;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(expr) = this.downcast_mut::<Option<Expr>>() {
+            expr.get_or_insert(Expr);
+        } else if let Some(stmts) = this.downcast_mut::<Vec<Stmt>>() {
+            stmts.push(Stmt { expr: None });
+        } else if this.is::<Expr>() {
+            self.exprs += 1;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_visit_containers_mut() {
+    let mut block = block();
+    let mut visitor = FillEmpty { exprs: 0 };
+    assert!(block.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(block.stmts.len(), 3);
+    assert!(block.stmts.iter().all(|stmt| stmt.expr.is_some()));
+    assert_eq!(visitor.exprs, 3);
+}
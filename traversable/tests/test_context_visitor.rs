@@ -0,0 +1,75 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::function::make_visitor_with_context;
+use traversable::function::make_visitor_with_context_mut;
+
+#[derive(Traversable, TraversableMut)]
+struct Block {
+    #[traverse(skip)]
+    depth: usize,
+    blocks: Vec<Block>,
+}
+
+fn block(blocks: Vec<Block>) -> Block {
+    Block { depth: 0, blocks }
+}
+
+#[test]
+fn test_context_visitor() {
+    let root = block(vec![block(vec![block(vec![])]), block(vec![])]);
+
+    // (current depth, max depth)
+    let visitor = make_visitor_with_context::<Block, _, (), _, _>(
+        (0, 0),
+        |(depth, max): &mut (usize, usize), _| {
+            *depth += 1;
+            *max = (*max).max(*depth);
+            ControlFlow::Continue(())
+        },
+        |(depth, _), _| {
+            *depth -= 1;
+            ControlFlow::Continue(())
+        },
+    );
+    assert_eq!(root.visit_with(visitor), Ok((0, 3)));
+}
+
+#[test]
+fn test_context_visitor_mut() {
+    let mut root = block(vec![block(vec![block(vec![])]), block(vec![])]);
+
+    let mut visitor = make_visitor_with_context_mut::<Block, _, (), _, _>(
+        0,
+        |depth: &mut usize, block| {
+            block.depth = *depth;
+            *depth += 1;
+            ControlFlow::Continue(())
+        },
+        |depth, _| {
+            *depth -= 1;
+            ControlFlow::Continue(())
+        },
+    );
+    assert!(root.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.into_context(), 0);
+    assert_eq!(root.blocks[0].blocks[0].depth, 2);
+    assert_eq!(root.blocks[1].depth, 1);
+}
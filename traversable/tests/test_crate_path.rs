@@ -0,0 +1,60 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "derive")]
+
+use core::any::Any;
+use core::ops::ControlFlow;
+
+// A framework re-exporting the crate under another path.
+mod framework {
+    pub mod reexport {
+        pub use traversable::*;
+    }
+}
+
+use framework::reexport::Traversable;
+use framework::reexport::TraversableMut;
+use framework::reexport::Visitor;
+
+#[derive(Traversable, TraversableMut)]
+#[traverse(crate = "framework::reexport")]
+struct Node {
+    children: (Leaf, Leaf),
+}
+
+#[derive(Traversable, TraversableMut)]
+#[traverse(crate = "framework::reexport")]
+struct Leaf;
+
+struct Count(usize);
+
+impl Visitor for Count {
+    type Break = ();
+
+    fn enter(&mut self, _: &dyn Any) -> ControlFlow<Self::Break> {
+        self.0 += 1;
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_crate_path() {
+    let node = Node {
+        children: (Leaf, Leaf),
+    };
+    let mut visitor = Count(0);
+    assert!(node.traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.0, 3);
+}
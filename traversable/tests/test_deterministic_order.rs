@@ -0,0 +1,198 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use traversable::Edit;
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+use traversable::sorted;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Traversable, TraversableMut)]
+struct Ident(#[traverse(skip)] String);
+
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl Visitor for Recorder {
+    type Break = ();
+
+    const DETERMINISTIC_ORDER: bool = true;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(Ident(name)) = this.downcast_ref::<Ident>() {
+            self.0.push(name.clone());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl VisitorMut for Recorder {
+    type Break = ();
+
+    const DETERMINISTIC_ORDER: bool = true;
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        Visitor::enter(self, this)
+    }
+}
+
+fn idents() -> impl DoubleEndedIterator<Item = Ident> {
+    (0..64).map(|i| Ident(format!("ident{i}")))
+}
+
+// The names of the idents in ascending order, e.g., `ident10` before `ident2`.
+fn sorted_names() -> Vec<String> {
+    let mut names = idents().map(|Ident(name)| name).collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[derive(Traversable, TraversableMut)]
+struct Scope {
+    #[traverse(with_ref = "sorted::hash_set", with_mut = "sorted::hash_set_mut")]
+    names: HashSet<Ident>,
+    #[traverse(with_ref = "sorted::hash_map", with_mut = "sorted::hash_map_mut")]
+    values: HashMap<Ident, Ident>,
+}
+
+#[test]
+fn test_hash_set() {
+    let forward = idents().collect::<HashSet<_>>();
+    let backward = idents().rev().collect::<HashSet<_>>();
+
+    let mut first = Recorder::default();
+    let mut second = Recorder::default();
+    assert!(sorted::hash_set(&forward, &mut first).is_continue());
+    assert!(sorted::hash_set(&backward, &mut second).is_continue());
+    assert_eq!(first.0, sorted_names());
+    assert_eq!(first.0, second.0);
+
+    let mut forward = forward;
+    let mut third = Recorder::default();
+    assert!(sorted::hash_set_mut(&mut forward, &mut third).is_continue());
+    assert_eq!(first.0, third.0);
+    assert_eq!(forward.len(), 64);
+}
+
+#[test]
+fn test_hash_map() {
+    let forward = idents().map(|i| (i, ())).collect::<HashMap<_, _>>();
+    let backward = idents().rev().map(|i| (i, ())).collect::<HashMap<_, _>>();
+
+    let mut first = Recorder::default();
+    let mut second = Recorder::default();
+    assert!(sorted::hash_map(&forward, &mut first).is_continue());
+    assert!(sorted::hash_map(&backward, &mut second).is_continue());
+    assert_eq!(first.0, sorted_names());
+    assert_eq!(first.0, second.0);
+
+    let mut forward = forward;
+    let mut third = Recorder::default();
+    assert!(sorted::hash_map_keys_mut(&mut forward, &mut third).is_continue());
+    assert_eq!(first.0, third.0);
+
+    let mut values = idents().map(|i| (i.clone(), i)).collect::<HashMap<_, _>>();
+    let mut fourth = Recorder::default();
+    assert!(sorted::hash_map_mut(&mut values, &mut fourth).is_continue());
+    assert_eq!(first.0, fourth.0);
+}
+
+#[test]
+fn test_hash_map_remove() {
+    // Removes the values with an odd index, in the sorted order of their keys.
+    #[derive(Default)]
+    struct RemoveOdd(usize);
+
+    impl VisitorMut for RemoveOdd {
+        type Break = ();
+
+        const DETERMINISTIC_ORDER: bool = true;
+        const EDITS: bool = true;
+
+        fn edit_mut(&mut self, this: &mut dyn Any) -> Edit {
+            if !this.is::<Ident>() {
+                return Edit::Keep;
+            }
+            self.0 += 1;
+            if self.0 % 2 == 0 {
+                Edit::Remove
+            } else {
+                Edit::Keep
+            }
+        }
+    }
+
+    let mut values = idents().map(|i| (i.clone(), i)).collect::<HashMap<_, _>>();
+    assert!(sorted::hash_map_mut(&mut values, &mut RemoveOdd::default()).is_continue());
+    let mut remaining = values
+        .into_keys()
+        .map(|Ident(name)| name)
+        .collect::<Vec<_>>();
+    remaining.sort();
+    let expected = sorted_names().into_iter().step_by(2).collect::<Vec<_>>();
+    assert_eq!(remaining, expected);
+}
+
+#[test]
+fn test_derive() {
+    let mut scope = Scope {
+        names: idents().collect(),
+        values: idents().map(|i| (i.clone(), i)).collect(),
+    };
+    let mut visitor = Recorder::default();
+    assert!(scope.traverse(&mut visitor).is_continue());
+    let mut expected = sorted_names();
+    expected.extend(
+        sorted_names()
+            .into_iter()
+            .flat_map(|name| [name.clone(), name]),
+    );
+    assert_eq!(visitor.0, expected);
+
+    let mut visitor = Recorder::default();
+    assert!(scope.traverse_mut(&mut visitor).is_continue());
+    let mut expected = sorted_names();
+    expected.extend(sorted_names());
+    assert_eq!(visitor.0, expected);
+}
+
+#[test]
+fn test_unordered_keys() {
+    // Hash-based collections of elements that are not `Ord` are traversed in iteration order.
+    #[derive(PartialEq, Eq, Hash, Traversable, TraversableMut)]
+    struct Key(#[traverse(skip)] String);
+
+    #[derive(Traversable, TraversableMut)]
+    struct Table {
+        keys: HashSet<Key>,
+        rows: HashMap<Key, u32>,
+    }
+
+    let mut table = Table {
+        keys: idents().map(|Ident(name)| Key(name)).collect(),
+        rows: idents().map(|Ident(name)| (Key(name), 0)).collect(),
+    };
+    let mut visitor = Recorder::default();
+    assert!(table.traverse(&mut visitor).is_continue());
+    assert!(table.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(table.keys.len(), 64);
+}
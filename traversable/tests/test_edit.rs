@@ -0,0 +1,131 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
+use traversable::Edit;
+use traversable::TraversableMut;
+use traversable::VisitorMut;
+
+#[derive(Debug, Clone, PartialEq, TraversableMut)]
+enum Stmt {
+    Nop,
+    Print(#[traverse(skip)] &'static str),
+    Twice(Box<Stmt>),
+    Block(Vec<Stmt>),
+}
+
+// Removes `Nop`s, unrolls `Twice` into two statements, and replaces empty blocks with `Nop`s.
+struct Simplify;
+
+impl VisitorMut for Simplify {
+    type Break = ();
+
+    const EDITS: bool = true;
+
+    fn edit_mut(&mut self, this: &mut dyn Any) -> Edit {
+        let Some(stmt) = this.downcast_mut::<Stmt>() else {
+            return Edit::Keep;
+        };
+        match stmt {
+            Stmt::Nop => Edit::Remove,
+            Stmt::Twice(inner) => {
+                let inner = (**inner).clone();
+                *stmt = inner.clone();
+                Edit::insert_after(inner)
+            }
+            Stmt::Block(stmts) if stmts.is_empty() => Edit::replace(Stmt::Nop),
+            _ => Edit::Keep,
+        }
+    }
+}
+
+#[test]
+fn test_edit_vec() {
+    let mut stmts = vec![
+        Stmt::Print("a"),
+        Stmt::Nop,
+        Stmt::Twice(Box::new(Stmt::Print("b"))),
+        Stmt::Block(vec![Stmt::Nop]),
+        Stmt::Nop,
+    ];
+    assert!(stmts.traverse_mut(&mut Simplify).is_continue());
+    assert_eq!(
+        stmts,
+        vec![
+            Stmt::Print("a"),
+            Stmt::Print("b"),
+            Stmt::Print("b"),
+            Stmt::Nop,
+        ]
+    );
+
+    let mut stmts = VecDeque::from([Stmt::Nop, Stmt::Print("a")]);
+    assert!(stmts.traverse_mut(&mut Simplify).is_continue());
+    assert_eq!(stmts, VecDeque::from([Stmt::Print("a")]));
+}
+
+#[test]
+fn test_edit_option() {
+    let mut stmt = Some(Stmt::Nop);
+    assert!(stmt.traverse_mut(&mut Simplify).is_continue());
+    assert_eq!(stmt, None);
+
+    let mut stmt = Some(Stmt::Block(vec![]));
+    assert!(stmt.traverse_mut(&mut Simplify).is_continue());
+    assert_eq!(stmt, Some(Stmt::Nop));
+}
+
+#[test]
+fn test_edit_map() {
+    let mut stmts = BTreeMap::from([(1, Stmt::Nop), (2, Stmt::Block(vec![]))]);
+    assert!(stmts.traverse_mut(&mut Simplify).is_continue());
+    assert_eq!(stmts, BTreeMap::from([(2, Stmt::Nop)]));
+}
+
+#[test]
+fn test_edit_unsupported() {
+    // Inserts everywhere, with a value of the wrong type for `Vec<Stmt>`.
+    struct Unsupported(bool);
+
+    impl VisitorMut for Unsupported {
+        type Break = ();
+
+        const EDITS: bool = true;
+
+        fn edit_mut(&mut self, _: &mut dyn Any) -> Edit {
+            if self.0 {
+                Edit::insert_after(Stmt::Nop)
+            } else {
+                Edit::replace("not a statement")
+            }
+        }
+    }
+
+    let mut stmts = vec![Stmt::Print("a")];
+    assert!(stmts.traverse_mut(&mut Unsupported(false)).is_continue());
+    assert_eq!(stmts, vec![Stmt::Print("a")]);
+
+    let mut stmt = Some(Stmt::Print("a"));
+    assert!(stmt.traverse_mut(&mut Unsupported(true)).is_continue());
+    assert_eq!(stmt, Some(Stmt::Print("a")));
+
+    let mut stmts = BTreeMap::from([(1, Stmt::Print("a"))]);
+    assert!(stmts.traverse_mut(&mut Unsupported(true)).is_continue());
+    assert_eq!(stmts, BTreeMap::from([(1, Stmt::Print("a"))]));
+}
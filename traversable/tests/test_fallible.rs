@@ -0,0 +1,148 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+use traversable::fallible::Fallible;
+use traversable::fallible::TryVisitor;
+use traversable::fallible::TryVisitorMut;
+use traversable::function::make_try_visitor_enter;
+use traversable::function::make_try_visitor_enter_mut;
+
+#[derive(Traversable, TraversableMut)]
+struct Ident(#[traverse(skip)] String);
+
+fn check(ident: &Ident) -> Result<(), String> {
+    if ident.0.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(())
+    } else {
+        Err(format!("invalid identifier `{}`", ident.0))
+    }
+}
+
+// Checks identifiers, then counts them.
+#[derive(Default)]
+struct Check(usize);
+
+impl Visitor for Check {
+    type Break = String;
+}
+
+impl TryVisitor for Check {
+    fn try_enter(&mut self, this: &dyn Any) -> Result<(), Self::Break> {
+        if let Some(ident) = this.downcast_ref::<Ident>() {
+            check(ident)?;
+            self.0 += 1;
+        }
+        Ok(())
+    }
+}
+
+impl VisitorMut for Check {
+    type Break = String;
+}
+
+impl TryVisitorMut for Check {
+    fn try_enter_mut(&mut self, this: &mut dyn Any) -> Result<(), Self::Break> {
+        if let Some(ident) = this.downcast_mut::<Ident>() {
+            check(ident)?;
+            ident.0.make_ascii_lowercase();
+        }
+        Ok(())
+    }
+}
+
+fn idents(names: &[&str]) -> Vec<Ident> {
+    names.iter().map(|name| Ident(name.to_string())).collect()
+}
+
+#[test]
+fn test_try_visitor() {
+    let mut visitor = Fallible::new(Check::default());
+    assert_eq!(idents(&["a", "b"]).try_traverse(&mut visitor), Ok(()));
+    assert_eq!(visitor.into_inner().0, 2);
+
+    let mut visitor = Fallible::new(Check::default());
+    assert_eq!(
+        idents(&["a", "b1", "c"]).try_traverse(&mut visitor),
+        Err("invalid identifier `b1`".to_owned())
+    );
+    assert_eq!(visitor.into_inner().0, 1);
+
+    let mut data = idents(&["A", "B"]);
+    assert_eq!(
+        data.try_traverse_mut(&mut Fallible::new(Check::default())),
+        Ok(())
+    );
+    assert_eq!(data[1].0, "b");
+}
+
+#[test]
+fn test_try_closure_visitor() {
+    let mut visitor = make_try_visitor_enter(check);
+    assert_eq!(
+        idents(&["a", "_"]).try_traverse(&mut visitor),
+        Err("invalid identifier `_`".to_owned())
+    );
+
+    let mut data = idents(&["A", "B2"]);
+    let mut visitor = make_try_visitor_enter_mut(|ident: &mut Ident| {
+        check(ident)?;
+        ident.0.make_ascii_lowercase();
+        Ok::<_, String>(())
+    });
+    assert!(data.try_traverse_mut(&mut visitor).is_err());
+    assert_eq!(data[0].0, "a");
+    assert_eq!(data[1].0, "B2");
+}
+
+#[test]
+fn test_try_visitor_leaf_hooks() {
+    // Sums integers, failing on overflow.
+    #[derive(Default)]
+    struct Sum(u8);
+
+    impl Visitor for Sum {
+        type Break = String;
+
+        const VISIT_TRIVIAL: bool = true;
+
+        fn visit_u8(&mut self, value: u8) -> ControlFlow<Self::Break> {
+            match self.0.checked_add(value) {
+                Some(sum) => self.0 = sum,
+                None => return ControlFlow::Break("overflow".to_owned()),
+            }
+            ControlFlow::Continue(())
+        }
+    }
+
+    impl TryVisitor for Sum {}
+
+    let mut visitor = Fallible::new(Sum::default());
+    assert_eq!(vec![1u8, 2, 3].try_traverse(&mut visitor), Ok(()));
+    assert_eq!(visitor.into_inner().0, 6);
+
+    let mut visitor = Fallible::new(Sum::default());
+    assert_eq!(
+        vec![200u8, 100].try_traverse(&mut visitor),
+        Err("overflow".to_owned())
+    );
+}
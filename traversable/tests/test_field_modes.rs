@@ -0,0 +1,78 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+mod common;
+
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use common::CollectLeaves;
+use common::Leaf;
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+
+#[derive(Traversable)]
+struct Config {
+    leaf: Leaf,
+}
+
+#[derive(Traversable, TraversableMut)]
+struct Node {
+    #[traverse(skip_mut)]
+    config: Arc<Config>,
+    #[traverse(skip_ref)]
+    scratch: Leaf,
+    #[traverse(with_ref = "traverse_first", with_mut = "traverse_last_mut")]
+    leaves: Vec<Leaf>,
+}
+
+fn traverse_first<V: Visitor>(leaves: &[Leaf], visitor: &mut V) -> ControlFlow<V::Break> {
+    match leaves.first() {
+        Some(leaf) => leaf.traverse(visitor),
+        None => ControlFlow::Continue(()),
+    }
+}
+
+fn traverse_last_mut<V: VisitorMut>(leaves: &mut [Leaf], visitor: &mut V) -> ControlFlow<V::Break> {
+    match leaves.last_mut() {
+        Some(leaf) => leaf.traverse_mut(visitor),
+        None => ControlFlow::Continue(()),
+    }
+}
+
+fn node() -> Node {
+    Node {
+        config: Arc::new(Config { leaf: Leaf(0) }),
+        scratch: Leaf(1),
+        leaves: vec![Leaf(2), Leaf(3), Leaf(4)],
+    }
+}
+
+#[test]
+fn test_field_modes_ref() {
+    let mut visitor = CollectLeaves::default();
+    assert!(node().traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.0, vec![0, 2]);
+}
+
+#[test]
+fn test_field_modes_mut() {
+    let mut visitor = CollectLeaves::default();
+    assert!(node().traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.0, vec![1, 4]);
+}
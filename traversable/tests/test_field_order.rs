@@ -0,0 +1,68 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+mod common;
+
+use common::Leaf;
+use common::collect;
+use traversable::Traversable;
+use traversable::TraversableMut;
+
+#[derive(Traversable, TraversableMut)]
+struct Let {
+    pattern: Leaf,
+    ty: Leaf,
+    #[traverse(order = 0)]
+    init: Leaf,
+}
+
+#[derive(Traversable, TraversableMut)]
+#[traverse(reverse)]
+struct Call(Leaf, Leaf, Leaf);
+
+#[derive(Traversable, TraversableMut)]
+#[traverse(reverse)]
+enum Stmt {
+    Assign {
+        target: Leaf,
+        #[traverse(order = 2)]
+        value: Leaf,
+        #[traverse(order = 1)]
+        index: Leaf,
+    },
+}
+
+#[test]
+fn test_field_order() {
+    let let_ = Let {
+        pattern: Leaf(1),
+        ty: Leaf(2),
+        init: Leaf(3),
+    };
+    assert_eq!(collect(let_), vec![3, 1, 2]);
+}
+
+#[test]
+fn test_reverse() {
+    assert_eq!(collect(Call(Leaf(1), Leaf(2), Leaf(3))), vec![3, 2, 1]);
+
+    let assign = Stmt::Assign {
+        target: Leaf(1),
+        value: Leaf(2),
+        index: Leaf(3),
+    };
+    assert_eq!(collect(assign), vec![1, 2, 3]);
+}
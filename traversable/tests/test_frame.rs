@@ -0,0 +1,238 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::cell::Cell;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+use traversable::frame::FrameVisitor;
+use traversable::frame::FrameVisitorMut;
+use traversable::frame::Framed;
+
+#[derive(Traversable, TraversableMut)]
+enum Stmt {
+    Let(Name),
+    Use(Name),
+    Block(Vec<Stmt>),
+}
+
+#[derive(Traversable, TraversableMut)]
+struct Name(#[traverse(skip)] &'static str);
+
+// Collects the uses of names not bound in an enclosing scope, restoring the scope size saved when
+// entering a block.
+#[derive(Default)]
+struct Unbound {
+    scope: Vec<&'static str>,
+    unbound: Vec<&'static str>,
+}
+
+impl Unbound {
+    fn enter(&mut self, this: &dyn Any) -> usize {
+        match this.downcast_ref::<Stmt>() {
+            Some(Stmt::Let(Name(name))) => self.scope.push(name),
+            Some(Stmt::Use(Name(name))) if !self.scope.contains(name) => self.unbound.push(name),
+            _ => {}
+        }
+        self.scope.len()
+    }
+
+    fn leave(&mut self, this: &dyn Any, len: usize) {
+        if this.is::<Stmt>() {
+            assert!(self.scope.len() >= len);
+        }
+        if let Some(Stmt::Block(_)) = this.downcast_ref::<Stmt>() {
+            self.scope.truncate(len);
+        }
+    }
+}
+
+impl Visitor for Unbound {
+    type Break = ();
+}
+
+impl FrameVisitor for Unbound {
+    type Frame = usize;
+
+    fn enter_frame(&mut self, this: &dyn Any) -> ControlFlow<Self::Break, Self::Frame> {
+        ControlFlow::Continue(self.enter(this))
+    }
+
+    fn leave_frame(&mut self, this: &dyn Any, frame: Self::Frame) -> ControlFlow<Self::Break> {
+        self.leave(this, frame);
+        ControlFlow::Continue(())
+    }
+}
+
+impl VisitorMut for Unbound {
+    type Break = ();
+}
+
+impl FrameVisitorMut for Unbound {
+    type Frame = usize;
+
+    fn enter_frame_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break, Self::Frame> {
+        ControlFlow::Continue(self.enter(this))
+    }
+
+    fn leave_frame_mut(
+        &mut self,
+        this: &mut dyn Any,
+        frame: Self::Frame,
+    ) -> ControlFlow<Self::Break> {
+        self.leave(this, frame);
+        ControlFlow::Continue(())
+    }
+}
+
+fn program() -> Vec<Stmt> {
+    vec![
+        Stmt::Let(Name("a")),
+        Stmt::Block(vec![
+            Stmt::Let(Name("b")),
+            Stmt::Use(Name("a")),
+            Stmt::Use(Name("b")),
+        ]),
+        Stmt::Use(Name("b")),
+        Stmt::Block(vec![Stmt::Use(Name("c")), Stmt::Let(Name("c"))]),
+    ]
+}
+
+#[test]
+fn test_frame() {
+    let program = program();
+    let mut visitor = Framed::new(Unbound::default());
+    assert!(program.traverse(&mut visitor).is_continue());
+    let visitor = visitor.into_inner();
+    assert_eq!(visitor.unbound, ["b", "c"]);
+    assert_eq!(visitor.scope, ["a"]);
+}
+
+#[test]
+fn test_frame_mut() {
+    let mut program = program();
+    let mut visitor = Framed::new(Unbound::default());
+    assert!(program.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.into_inner().unbound, ["b", "c"]);
+}
+
+// Sums the integers below each `Stmt`, using the typed leaf hook and the frame of the statement.
+#[derive(Default)]
+struct Sums {
+    sum: u64,
+    sums: Vec<u64>,
+}
+
+impl Visitor for Sums {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = true;
+
+    fn visit_u64(&mut self, value: u64) -> ControlFlow<Self::Break> {
+        self.sum += value;
+        ControlFlow::Continue(())
+    }
+}
+
+impl FrameVisitor for Sums {
+    type Frame = u64;
+
+    fn enter_frame(&mut self, _: &dyn Any) -> ControlFlow<Self::Break, Self::Frame> {
+        ControlFlow::Continue(self.sum)
+    }
+
+    fn leave_frame(&mut self, this: &dyn Any, frame: Self::Frame) -> ControlFlow<Self::Break> {
+        if this.is::<Expr>() {
+            self.sums.push(self.sum - frame);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Traversable)]
+enum Expr {
+    Int(u32),
+    Add(Box<Expr>, Box<Expr>),
+}
+
+#[test]
+fn test_frame_leaf_hooks() {
+    let expr = Expr::Add(
+        Box::new(Expr::Int(1)),
+        Box::new(Expr::Add(Box::new(Expr::Int(2)), Box::new(Expr::Int(3)))),
+    );
+    let mut visitor = Framed::new(Sums::default());
+    assert!(expr.traverse(&mut visitor).is_continue());
+    let visitor = visitor.into_inner();
+    assert_eq!(visitor.sum, 6);
+    assert_eq!(visitor.sums, [1, 2, 3, 5, 6]);
+}
+
+// Counts the frames that are alive, breaking when entering the name `stop`.
+struct Live(Rc<Cell<usize>>);
+
+impl Drop for Live {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
+    }
+}
+
+struct StopAt {
+    stop: &'static str,
+    live: Rc<Cell<usize>>,
+}
+
+impl Visitor for StopAt {
+    type Break = ();
+}
+
+impl FrameVisitor for StopAt {
+    type Frame = Live;
+
+    fn enter_frame(&mut self, this: &dyn Any) -> ControlFlow<Self::Break, Self::Frame> {
+        if let Some(Name(name)) = this.downcast_ref::<Name>() {
+            if *name == self.stop {
+                return ControlFlow::Break(());
+            }
+        }
+        self.live.set(self.live.get() + 1);
+        ControlFlow::Continue(Live(self.live.clone()))
+    }
+
+    fn leave_frame(&mut self, _: &dyn Any, _: Self::Frame) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_frame_reuse_after_break() {
+    let live = Rc::new(Cell::new(0));
+    let mut visitor = Framed::new(StopAt {
+        stop: "b",
+        live: live.clone(),
+    });
+    assert!(program().traverse(&mut visitor).is_break());
+    assert_eq!(live.get(), 0);
+
+    let program = vec![Stmt::Block(vec![Stmt::Use(Name("a"))])];
+    assert!(program.traverse(&mut visitor).is_continue());
+    assert_eq!(live.get(), 0);
+}
@@ -0,0 +1,108 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "std")]
+
+use std::collections::BTreeSet;
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+
+// Sums integers and floats, and collects strings.
+#[derive(Default)]
+struct Leaves {
+    ints: i128,
+    floats: f64,
+    strings: BTreeSet<String>,
+}
+
+impl Visitor for Leaves {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = true;
+    const VISIT_STD: bool = true;
+
+    fn visit_i64(&mut self, value: i64) -> ControlFlow<Self::Break> {
+        self.ints += i128::from(value);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_u64(&mut self, value: u64) -> ControlFlow<Self::Break> {
+        self.ints += i128::from(value);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_f64(&mut self, value: f64) -> ControlFlow<Self::Break> {
+        self.floats += value;
+        ControlFlow::Continue(())
+    }
+
+    fn visit_str(&mut self, value: &str) -> ControlFlow<Self::Break> {
+        self.strings.insert(value.to_owned());
+        ControlFlow::Continue(())
+    }
+}
+
+// Normalizes negative zeros and lowercases strings.
+struct Normalize;
+
+impl VisitorMut for Normalize {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = true;
+    const VISIT_STD: bool = true;
+
+    fn visit_f64_mut(&mut self, value: &mut f64) -> ControlFlow<Self::Break> {
+        if *value == 0.0 {
+            *value = 0.0;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_string_mut(&mut self, value: &mut String) -> ControlFlow<Self::Break> {
+        value.make_ascii_lowercase();
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_leaf_hooks() {
+    let data = (
+        1u8,
+        -2i32,
+        3usize,
+        0.5f32,
+        1.5f64,
+        vec![String::from("a"), String::from("b")],
+    );
+    let mut visitor = Leaves::default();
+    assert!(data.traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.ints, 2);
+    assert_eq!(visitor.floats, 2.0);
+    assert_eq!(
+        visitor.strings,
+        BTreeSet::from(["a".to_owned(), "b".to_owned()])
+    );
+}
+
+#[test]
+fn test_leaf_hooks_mut() {
+    let mut data = (-0.0f64, String::from("Hello"));
+    assert!(data.traverse_mut(&mut Normalize).is_continue());
+    assert!(data.0.is_sign_positive());
+    assert_eq!(data.1, "hello");
+}
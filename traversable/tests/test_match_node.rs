@@ -0,0 +1,98 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+use traversable::match_node;
+
+#[derive(Debug, PartialEq, Traversable, TraversableMut)]
+enum Literal {
+    Null,
+    Int(#[traverse(skip)] i64),
+}
+
+#[derive(Debug, PartialEq, Traversable, TraversableMut)]
+enum Expr {
+    Lit(Literal),
+    Call {
+        #[traverse(skip)]
+        name: String,
+        args: Vec<Expr>,
+    },
+}
+
+#[derive(Default)]
+struct Describe(Vec<String>);
+
+impl Visitor for Describe {
+    type Break = ();
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        let description = match_node!(this,
+            Literal::Int(n) if *n < 0 => format!("negative {n}"),
+            Literal::Int(n) => format!("int {n}"),
+            Literal::Null => "null".to_owned(),
+            Expr::Call { name, args } => format!("call {name}/{}", args.len()),
+            _ => return ControlFlow::Continue(()),
+        );
+        self.0.push(description);
+        ControlFlow::Continue(())
+    }
+}
+
+struct Negate;
+
+impl VisitorMut for Negate {
+    type Break = ();
+
+    fn enter_mut(&mut self, this: &mut dyn Any) -> ControlFlow<Self::Break> {
+        match_node!(mut this,
+            Literal::Int(n) => *n = -*n,
+            Expr::Call { name, .. } => {
+                name.make_ascii_uppercase();
+            }
+            Literal::Null if false => {}
+            _ => {}
+        );
+        ControlFlow::Continue(())
+    }
+}
+
+fn expr() -> Expr {
+    Expr::Call {
+        name: "f".to_owned(),
+        args: vec![Expr::Lit(Literal::Int(1)), Expr::Lit(Literal::Null)],
+    }
+}
+
+#[test]
+fn test_match_node() {
+    let mut expr = expr();
+
+    let mut visitor = Describe::default();
+    assert!(expr.traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.0, ["call f/2", "int 1", "null"]);
+
+    assert!(expr.traverse_mut(&mut Negate).is_continue());
+    let mut visitor = Describe::default();
+    assert!(expr.traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.0, ["call F/2", "negative -1", "null"]);
+}
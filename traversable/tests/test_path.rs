@@ -0,0 +1,117 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::ops::ControlFlow;
+
+use traversable::Edge;
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::function::make_visitor_enter_with_path;
+use traversable::function::make_visitor_with_path_mut;
+
+#[derive(Traversable, TraversableMut)]
+struct Ident(#[traverse(skip)] &'static str);
+
+#[derive(Traversable, TraversableMut)]
+enum Expr {
+    Var(Ident),
+    Call { func: Ident, args: Vec<Expr> },
+}
+
+#[derive(Traversable, TraversableMut)]
+struct Func {
+    name: Ident,
+    body: (Expr, Option<Box<Expr>>),
+}
+
+fn func() -> Func {
+    Func {
+        name: Ident("main"),
+        body: (
+            Expr::Call {
+                func: Ident("f"),
+                args: vec![Expr::Var(Ident("x")), Expr::Var(Ident("y"))],
+            },
+            Some(Box::new(Expr::Var(Ident("z")))),
+        ),
+    }
+}
+
+#[test]
+fn test_path() {
+    let mut idents = Vec::new();
+    let mut visitor = make_visitor_enter_with_path::<Ident, (), _>(|ident, path| {
+        idents.push((ident.0, path.depth(), path.to_string()));
+        ControlFlow::Continue(())
+    });
+    assert!(func().traverse(&mut visitor).is_continue());
+    assert_eq!(
+        idents,
+        [
+            ("main", 1, ".name".to_owned()),
+            ("f", 2, ".body[0].func".to_owned()),
+            ("x", 3, ".body[0].args[0][0]".to_owned()),
+            ("y", 3, ".body[0].args[1][0]".to_owned()),
+            ("z", 2, ".body[1][0]".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn test_path_mut() {
+    let mut func = func();
+    let mut visitor = make_visitor_with_path_mut::<Ident, (), _, _>(
+        |ident, path| {
+            if path.edges().last() == Some(&Edge::Field("func")) {
+                ident.0 = "g";
+            }
+            ControlFlow::Continue(())
+        },
+        |_, path| {
+            assert!(path.depth() > 0);
+            ControlFlow::Continue(())
+        },
+    );
+    assert!(func.traverse_mut(&mut visitor).is_continue());
+    let Expr::Call { func, .. } = &func.body.0 else {
+        unreachable!()
+    };
+    assert_eq!(func.0, "g");
+}
+
+#[test]
+fn test_path_reuse_after_break() {
+    let mut paths = Vec::new();
+    let mut visitor = make_visitor_enter_with_path::<Ident, (), _>(|ident, path| {
+        paths.push((path.depth(), path.to_string()));
+        if ident.0 == "x" {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert!(func().traverse(&mut visitor).is_break());
+    assert!(Ident("a").traverse(&mut visitor).is_continue());
+    assert_eq!(
+        paths,
+        [
+            (1, ".name".to_owned()),
+            (2, ".body[0].func".to_owned()),
+            (3, ".body[0].args[0][0]".to_owned()),
+            (0, "".to_owned()),
+        ]
+    );
+}
@@ -0,0 +1,82 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::cast::make_cast_visitor;
+use traversable::read_only::ReadOnly;
+
+#[derive(Traversable, TraversableMut)]
+enum Expr {
+    Lit(u32),
+    Neg(Box<Expr>),
+}
+
+// Counts negations and sums literals.
+#[derive(Default)]
+struct Stats {
+    negs: usize,
+    lits: u64,
+}
+
+impl Visitor for Stats {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = true;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        if let Some(Expr::Neg(_)) = this.downcast_ref::<Expr>() {
+            self.negs += 1;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_u64(&mut self, value: u64) -> ControlFlow<Self::Break> {
+        self.lits += value;
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_read_only() {
+    let mut expr = Expr::Neg(Box::new(Expr::Neg(Box::new(Expr::Lit(7)))));
+
+    let mut visitor = Stats::default();
+    assert!(expr.traverse(&mut visitor).is_continue());
+    assert_eq!((visitor.negs, visitor.lits), (2, 7));
+
+    let mut visitor = ReadOnly::new(Stats::default());
+    assert!(expr.traverse_mut(&mut visitor).is_continue());
+    let visitor = visitor.into_inner();
+    assert_eq!((visitor.negs, visitor.lits), (2, 7));
+}
+
+#[test]
+fn test_read_only_node_hooks() {
+    // Visitors that override the node hooks are rejected by `ReadOnly::new`.
+    fn node_hooks<V: Visitor>(_: &V) -> bool {
+        V::NODE_HOOKS
+    }
+
+    let visitor = make_cast_visitor::<dyn Debug, (), _>(|_| ControlFlow::Continue(()));
+    assert!(node_hooks(&visitor));
+    assert!(!node_hooks(&Stats::default()));
+}
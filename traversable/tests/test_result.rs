@@ -0,0 +1,55 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::function::make_visitor_enter;
+use traversable::function::make_visitor_enter_mut;
+
+#[derive(Debug, PartialEq, Traversable, TraversableMut)]
+struct Expr(#[traverse(skip)] u32);
+
+#[derive(Debug, PartialEq, Traversable, TraversableMut)]
+struct Recovered(Expr);
+
+#[test]
+fn test_result() {
+    let ok: Result<Expr, Recovered> = Ok(Expr(1));
+    let err: Result<Expr, Recovered> = Err(Recovered(Expr(2)));
+
+    for (result, expected) in [(ok, 1), (err, 2)] {
+        let mut visited = vec![];
+        let mut visitor = make_visitor_enter::<Expr, (), _>(|expr| {
+            visited.push(expr.0);
+            ControlFlow::Continue(())
+        });
+        assert!(result.traverse(&mut visitor).is_continue());
+        assert_eq!(visited, vec![expected]);
+    }
+}
+
+#[test]
+fn test_result_mut() {
+    let mut result: Result<Expr, Recovered> = Err(Recovered(Expr(2)));
+    let mut visitor = make_visitor_enter_mut::<Expr, (), _>(|expr| {
+        expr.0 += 1;
+        ControlFlow::Continue(())
+    });
+    assert!(result.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(result, Err(Recovered(Expr(3))));
+}
@@ -0,0 +1,115 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::any::TypeId;
+use std::ops::ControlFlow;
+
+use traversable::Edge;
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::schema::SchemaKind;
+
+#[derive(Traversable, TraversableMut)]
+struct Leaf(u32);
+
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+struct Node<T> {
+    children: Vec<T>,
+    #[traverse(skip)]
+    id: usize,
+}
+
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+enum Expr {
+    Leaf(Leaf),
+    Pair {
+        r#left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    #[traverse(skip)]
+    Error,
+}
+
+#[test]
+fn test_struct_schema() {
+    let schema = Node::<Leaf>::SCHEMA.unwrap();
+    assert_eq!(schema.name, "Node");
+    assert!(matches!(schema.kind, SchemaKind::Struct(_)));
+    assert!(schema.variants().is_empty());
+
+    let fields = schema.fields();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].edge, Edge::Field("children"));
+    assert_eq!((fields[0].type_id)(), TypeId::of::<Vec<Leaf>>());
+    assert_eq!((fields[0].type_name)(), std::any::type_name::<Vec<Leaf>>());
+    assert!(!fields[0].skipped);
+    assert_eq!(fields[1].edge, Edge::Field("id"));
+    assert!(fields[1].skipped);
+
+    let schema = Leaf::SCHEMA.unwrap();
+    assert_eq!(schema.fields()[0].edge, Edge::Index(0));
+    assert!(Vec::<Leaf>::SCHEMA.is_none());
+}
+
+#[test]
+fn test_enum_schema() {
+    let schema = Expr::SCHEMA.unwrap();
+    assert_eq!(schema.name, "Expr");
+    assert!(schema.fields().is_empty());
+
+    let variants = schema.variants();
+    let names: Vec<_> = variants.iter().map(|variant| variant.name).collect();
+    assert_eq!(names, ["Leaf", "Pair", "Error"]);
+    assert_eq!(variants[1].fields[0].edge, Edge::Field("left"));
+    assert_eq!((variants[1].fields[1].type_id)(), TypeId::of::<Box<Expr>>());
+    assert!(!variants[1].skipped);
+    assert!(variants[2].skipped);
+    assert!(variants[2].fields.is_empty());
+}
+
+struct TypeNames(Vec<&'static str>);
+
+impl Visitor for TypeNames {
+    type Break = ();
+
+    const NODE_HOOKS: bool = true;
+
+    fn enter_node<T: Traversable>(&mut self, _: &T) -> ControlFlow<Self::Break> {
+        if let Some(schema) = T::SCHEMA {
+            self.0.push(schema.name);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn enter(&mut self, _: &dyn Any) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_schema_in_visitor() {
+    let node = Node {
+        children: vec![Leaf(1), Leaf(2)],
+        id: 0,
+    };
+    let mut visitor = TypeNames(Vec::new());
+    assert!(node.traverse(&mut visitor).is_continue());
+    assert_eq!(visitor.0, ["Node", "Leaf", "Leaf"]);
+}
@@ -0,0 +1,91 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+mod common;
+
+use common::Leaf;
+use common::collect;
+use traversable::Traversable;
+use traversable::TraversableMut;
+
+#[derive(Traversable, TraversableMut)]
+struct Expr {
+    value: Leaf,
+    #[traverse(skip_if = "Expr::is_unresolved")]
+    resolved_type: Leaf,
+    #[traverse(skip)]
+    resolved: bool,
+}
+
+impl Expr {
+    fn is_unresolved(&self) -> bool {
+        !self.resolved
+    }
+}
+
+#[derive(Traversable, TraversableMut)]
+enum Item {
+    #[traverse(skip_if = "Item::is_hidden")]
+    Private {
+        hidden: bool,
+        leaf: Leaf,
+    },
+    Public(Leaf, #[traverse(skip_if = "Item::is_undocumented")] Leaf),
+}
+
+impl Item {
+    fn is_hidden(&self) -> bool {
+        matches!(self, Item::Private { hidden: true, .. })
+    }
+
+    fn is_undocumented(&self) -> bool {
+        matches!(self, Item::Public(_, Leaf(0)))
+    }
+}
+
+#[test]
+fn test_skip_if_field() {
+    let unresolved = Expr {
+        value: Leaf(1),
+        resolved_type: Leaf(2),
+        resolved: false,
+    };
+    assert_eq!(collect(unresolved), vec![1]);
+
+    let resolved = Expr {
+        value: Leaf(1),
+        resolved_type: Leaf(2),
+        resolved: true,
+    };
+    assert_eq!(collect(resolved), vec![1, 2]);
+}
+
+#[test]
+fn test_skip_if_variant() {
+    let items = vec![
+        Item::Private {
+            hidden: true,
+            leaf: Leaf(1),
+        },
+        Item::Private {
+            hidden: false,
+            leaf: Leaf(2),
+        },
+        Item::Public(Leaf(3), Leaf(0)),
+        Item::Public(Leaf(4), Leaf(5)),
+    ];
+    assert_eq!(collect(items), vec![2, 3, 4, 5]);
+}
@@ -0,0 +1,79 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::any::Any;
+use core::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+
+// Counts visited nodes, visiting trivial and primary standard library types if `ALL` is set.
+struct Count<const ALL: bool>(usize);
+
+impl<const ALL: bool> Visitor for Count<ALL> {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = ALL;
+    const VISIT_STD: bool = ALL;
+
+    fn enter(&mut self, _: &dyn Any) -> ControlFlow<Self::Break> {
+        self.0 += 1;
+        ControlFlow::Continue(())
+    }
+}
+
+impl<const ALL: bool> VisitorMut for Count<ALL> {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = ALL;
+    const VISIT_STD: bool = ALL;
+
+    fn enter_mut(&mut self, _: &mut dyn Any) -> ControlFlow<Self::Break> {
+        self.0 += 1;
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn test_visit_trivial() {
+    let mut data = (1u8, 2i64, 3.0f32, 'c', true, ());
+
+    let mut visitor = Count::<false>(0);
+    assert!(data.traverse(&mut visitor).is_continue());
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.0, 0);
+
+    let mut visitor = Count::<true>(0);
+    assert!(data.traverse(&mut visitor).is_continue());
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.0, 12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_visit_std() {
+    let mut data = vec![String::from("a"), String::from("b")];
+
+    let mut visitor = Count::<false>(0);
+    assert!(data.traverse(&mut visitor).is_continue());
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.0, 0);
+
+    let mut visitor = Count::<true>(0);
+    assert!(data.traverse(&mut visitor).is_continue());
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.0, 4);
+}
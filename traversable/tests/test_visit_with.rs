@@ -0,0 +1,82 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::ops::ControlFlow;
+
+use traversable::Finish;
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::function::make_fold_visitor;
+use traversable::function::make_fold_visitor_mut;
+
+#[derive(Traversable, TraversableMut)]
+struct Item(#[traverse(skip)] u32);
+
+// Sums items, breaking on the first zero.
+#[derive(Default)]
+struct Sum(u32);
+
+impl Visitor for Sum {
+    type Break = &'static str;
+
+    fn enter(&mut self, this: &dyn Any) -> ControlFlow<Self::Break> {
+        match this.downcast_ref::<Item>() {
+            Some(Item(0)) => ControlFlow::Break("zero"),
+            Some(Item(n)) => {
+                self.0 += n;
+                ControlFlow::Continue(())
+            }
+            None => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl Finish for Sum {
+    type Output = u32;
+
+    fn finish(self) -> Self::Output {
+        self.0
+    }
+}
+
+#[test]
+fn test_visit_with() {
+    assert_eq!(vec![Item(1), Item(2)].visit_with(Sum::default()), Ok(3));
+    assert_eq!(
+        vec![Item(1), Item(0)].visit_with(Sum::default()),
+        Err("zero")
+    );
+}
+
+#[test]
+fn test_fold_visitor() {
+    let items = vec![Item(1), Item(2), Item(3)];
+    let visitor = make_fold_visitor::<Item, _, (), _>(Vec::new(), |acc, item| {
+        acc.push(item.0);
+        ControlFlow::Continue(())
+    });
+    assert_eq!(items.visit_with(visitor), Ok(vec![1, 2, 3]));
+
+    let mut items = items;
+    let visitor = make_fold_visitor_mut::<Item, _, (), _>(0, |acc, item| {
+        item.0 *= 2;
+        *acc += item.0;
+        ControlFlow::Continue(())
+    });
+    assert_eq!(items.visit_with_mut(visitor), Ok(12));
+}
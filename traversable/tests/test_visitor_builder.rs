@@ -0,0 +1,107 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::ops::ControlFlow;
+
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::function::VisitorBuilder;
+use traversable::function::VisitorMutBuilder;
+
+#[derive(Debug, PartialEq, Traversable, TraversableMut)]
+enum Expr {
+    Lit(#[traverse(skip)] i64),
+    Neg(Box<Expr>),
+}
+
+#[derive(Debug, PartialEq, Traversable, TraversableMut)]
+struct Stmt {
+    expr: Expr,
+}
+
+fn program() -> Vec<Stmt> {
+    vec![
+        Stmt {
+            expr: Expr::Neg(Box::new(Expr::Lit(1))),
+        },
+        Stmt { expr: Expr::Lit(2) },
+    ]
+}
+
+#[test]
+fn test_visitor_builder() {
+    let mut events = Vec::new();
+    let mut stmts = 0;
+    let mut visitor = VisitorBuilder::<()>::new()
+        .on_enter::<Expr>(|expr| {
+            if let Expr::Lit(n) = expr {
+                events.push(*n);
+            }
+            ControlFlow::Continue(())
+        })
+        .on_leave::<Stmt>(|_| {
+            stmts += 1;
+            ControlFlow::Continue(())
+        })
+        .build();
+    assert!(program().traverse(&mut visitor).is_continue());
+    drop(visitor);
+    assert_eq!(events, [1, 2]);
+    assert_eq!(stmts, 2);
+
+    let result = program().visit_with(
+        VisitorBuilder::new()
+            .on_enter::<Expr>(|expr| match expr {
+                Expr::Lit(n) if *n > 1 => ControlFlow::Break(*n),
+                _ => ControlFlow::Continue(()),
+            })
+            .build(),
+    );
+    assert_eq!(result, Err(2));
+}
+
+#[test]
+fn test_visitor_mut_builder() {
+    let mut program = program();
+    let mut visitor = VisitorMutBuilder::<()>::new()
+        .on_enter::<Expr>(|expr| {
+            if let Expr::Neg(inner) = expr {
+                if let Expr::Lit(n) = **inner {
+                    *expr = Expr::Lit(-n);
+                }
+            }
+            ControlFlow::Continue(())
+        })
+        .on_leave::<Expr>(|expr| {
+            if let Expr::Lit(n) = expr {
+                *n *= 10;
+            }
+            ControlFlow::Continue(())
+        })
+        .build();
+    assert!(program.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(
+        program,
+        [
+            Stmt {
+                expr: Expr::Lit(-10)
+            },
+            Stmt {
+                expr: Expr::Lit(20)
+            },
+        ]
+    );
+}
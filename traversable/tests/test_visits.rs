@@ -0,0 +1,166 @@
+// Copyright 2025 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use traversable::Edge;
+use traversable::Edit;
+use traversable::Traversable;
+use traversable::TraversableMut;
+use traversable::Visitor;
+use traversable::VisitorMut;
+use traversable::Visits;
+
+#[derive(Traversable, TraversableMut)]
+struct Node {
+    bytes: Bytes,
+}
+
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+#[traverse(skip, visits = "fields")]
+struct Bytes {
+    data: Vec<u8>,
+    name: String,
+    #[traverse(skip)]
+    node: Option<Box<Node>>,
+}
+
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+#[traverse(skip, visits = "fields")]
+enum Leaf {
+    Int(u64),
+    #[traverse(skip)]
+    Node(Node),
+}
+
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+#[traverse(skip, visits = "fields")]
+enum List {
+    Cons(u32, Box<List>),
+    Nil,
+}
+
+// Types that are not visited themselves and refer to each other.
+#[allow(dead_code)]
+#[derive(Traversable, TraversableMut)]
+#[traverse(skip)]
+enum Expr {
+    Int(u64),
+    Block(Block),
+}
+
+#[derive(Traversable, TraversableMut)]
+#[traverse(skip)]
+struct Block {
+    exprs: Box<Option<Expr>>,
+}
+
+// Counts the edges entered and the edits requested in mutable traversals, visiting trivial types
+// if `TRIVIAL` is set and applying edits if `EDITS` is set.
+#[derive(Default)]
+struct Calls<const TRIVIAL: bool, const EDITS: bool> {
+    edges: usize,
+    edits: usize,
+}
+
+impl<const TRIVIAL: bool, const EDITS: bool> Visitor for Calls<TRIVIAL, EDITS> {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = TRIVIAL;
+    const VISIT_STD: bool = false;
+    const VISIT_CONTAINERS: bool = false;
+}
+
+impl<const TRIVIAL: bool, const EDITS: bool> VisitorMut for Calls<TRIVIAL, EDITS> {
+    type Break = ();
+
+    const VISIT_TRIVIAL: bool = TRIVIAL;
+    const VISIT_STD: bool = false;
+    const VISIT_CONTAINERS: bool = false;
+    const EDITS: bool = EDITS;
+
+    fn enter_edge_mut(&mut self, _: Edge) {
+        self.edges += 1;
+    }
+
+    fn edit_mut(&mut self, _: &mut dyn Any) -> Edit {
+        self.edits += 1;
+        Edit::Keep
+    }
+}
+
+#[test]
+fn test_visits() {
+    assert_eq!(<u8 as Traversable>::VISITS, Visits::TRIVIAL);
+    assert_eq!(<String as Traversable>::VISITS, Visits::STD);
+    assert_eq!(
+        <HashMap<u64, Vec<u8>> as Traversable>::VISITS,
+        Visits::TRIVIAL.union(Visits::CONTAINERS),
+    );
+    assert_eq!(
+        <Vec<u8> as TraversableMut>::VISITS,
+        Visits::TRIVIAL.union(Visits::CONTAINERS),
+    );
+    assert_eq!(<Box<u8> as Traversable>::VISITS, Visits::TRIVIAL);
+    assert_eq!(<Node as Traversable>::VISITS, Visits::ANY);
+    assert_eq!(
+        <Bytes as Traversable>::VISITS,
+        Visits::TRIVIAL.union(Visits::CONTAINERS).union(Visits::STD),
+    );
+    assert_eq!(<Leaf as TraversableMut>::VISITS, Visits::TRIVIAL);
+    assert_eq!(<List as Traversable>::VISITS, Visits::ANY);
+    assert_eq!(<List as TraversableMut>::VISITS, Visits::ANY);
+    assert_eq!(<Expr as Traversable>::VISITS, Visits::ANY);
+    assert_eq!(<Block as TraversableMut>::VISITS, Visits::ANY);
+
+    assert!(!<Bytes as Traversable>::VISITS.may_call::<Calls<false, false>>());
+    assert!(<Bytes as Traversable>::VISITS.may_call::<Calls<true, false>>());
+    assert!(!Visits::NOTHING.may_call_mut::<Calls<true, false>>());
+    assert!(!Visits::TRIVIAL.may_call_mut::<Calls<false, false>>());
+    assert!(Visits::NOTHING.may_call_mut::<Calls<false, true>>());
+    assert!(Visits::ANY.may_call_mut::<Calls<false, false>>());
+}
+
+#[test]
+fn test_skip_elements() {
+    // Elements that are neither visited nor edited are skipped.
+    let mut data = vec![vec![0u8; 4]; 4];
+    let mut visitor = Calls::<false, false>::default();
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!((visitor.edges, visitor.edits), (0, 0));
+
+    // Elements that are visited are traversed, but not edited.
+    let mut visitor = Calls::<true, false>::default();
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!((visitor.edges, visitor.edits), (20, 0));
+
+    // Elements that may be edited are traversed, even if they are not visited.
+    let mut visitor = Calls::<false, true>::default();
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!((visitor.edges, visitor.edits), (20, 20));
+
+    let mut data = Some(Leaf::Int(0));
+    let mut visitor = Calls::<false, false>::default();
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.edits, 0);
+    let mut visitor = Calls::<false, true>::default();
+    assert!(data.traverse_mut(&mut visitor).is_continue());
+    assert_eq!(visitor.edits, 1);
+}